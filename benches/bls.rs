@@ -0,0 +1,112 @@
+use bls_sys::{bls_init, BlsId, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::convert::TryFrom;
+
+const CURVES: [CurveType; 6] = [
+    CurveType::CurveFp254BNb,
+    CurveType::CurveFp382One,
+    CurveType::CurveFp382Two,
+    CurveType::CurveFp462,
+    CurveType::CurveSNARK1,
+    CurveType::Bls12CurveFp381,
+];
+const AGGREGATION_SIZES: [usize; 4] = [10, 100, 1000, 10_000];
+const THRESHOLDS: [usize; 5] = [3, 10, 25, 50, 100];
+
+const MSG: &[u8] = b"benchmark message";
+
+/// Curves the linked library supports, the others fail to initialise and are skipped.
+fn supported_curves() -> Vec<CurveType> {
+    CURVES
+        .iter()
+        .cloned()
+        .filter(|curve| bls_init(*curve).is_ok())
+        .collect()
+}
+
+fn master_keys(threshold: usize) -> Vec<BlsSecretKey> {
+    (0..threshold)
+        .map(|_| BlsSecretKey::new_random().unwrap())
+        .collect()
+}
+
+fn sign_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sign_verify");
+    for curve in supported_curves() {
+        bls_init(curve).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let pk = sk.to_public_key();
+        let sig = sk.sign(MSG);
+        let name = format!("{:?}", curve);
+
+        group.bench_function(BenchmarkId::new("sign", &name), |b| b.iter(|| sk.sign(MSG)));
+        group.bench_function(BenchmarkId::new("verify", &name), |b| {
+            b.iter(|| sig.verify(&pk, MSG))
+        });
+        group.bench_function(BenchmarkId::new("to_public_key", &name), |b| {
+            b.iter(|| sk.to_public_key())
+        });
+    }
+    group.finish();
+}
+
+fn aggregation(c: &mut Criterion) {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    let mut group = c.benchmark_group("aggregation");
+    group.sample_size(10);
+    for n in AGGREGATION_SIZES.iter() {
+        let sks = master_keys(*n);
+        let pks = sks.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+        let sigs = sks.iter().map(|sk| sk.sign(MSG)).collect::<Vec<_>>();
+
+        group.bench_with_input(BenchmarkId::new("signatures", n), n, |b, _| {
+            b.iter(|| sigs.iter().sum::<BlsSignature>())
+        });
+        group.bench_with_input(BenchmarkId::new("public_keys", n), n, |b, _| {
+            b.iter(|| pks.iter().sum::<BlsPublicKey>())
+        });
+    }
+    group.finish();
+}
+
+fn sharing_recovery(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sharing_recovery");
+    group.sample_size(10);
+    for curve in supported_curves() {
+        bls_init(curve).unwrap();
+
+        for threshold in THRESHOLDS.iter() {
+            let msk = master_keys(*threshold);
+            let mpk = msk.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+            let ids = (1..=*threshold as u64)
+                .map(|id| BlsId::try_from(id).unwrap())
+                .collect::<Vec<BlsId>>();
+            let shares = ids
+                .iter()
+                .map(|id| BlsSecretKey::new_share(&msk[0], *threshold, id).unwrap())
+                .collect::<Vec<_>>();
+            let sigs = shares.iter().map(|sk| sk.sign(MSG)).collect::<Vec<_>>();
+            let id = BlsId::try_from(*threshold as u64 + 1).unwrap();
+            let name = format!("{:?}/{}", curve, threshold);
+
+            group.bench_function(BenchmarkId::new("new_share_sk", &name), |b| {
+                b.iter(|| BlsSecretKey::new_share(&msk[0], *threshold, &id).unwrap())
+            });
+            group.bench_function(BenchmarkId::new("new_share_pk", &name), |b| {
+                b.iter(|| BlsPublicKey::new_share(&mpk[0], *threshold, &id).unwrap())
+            });
+            group.bench_function(BenchmarkId::new("recover_sk", &name), |b| {
+                b.iter(|| BlsSecretKey::recover(&shares, &ids, *threshold).unwrap())
+            });
+            group.bench_function(BenchmarkId::new("recover_sig", &name), |b| {
+                b.iter(|| BlsSignature::recover(&sigs, &ids, *threshold).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, sign_verify, aggregation, sharing_recovery);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+use bls_sys::{bls_init, CurveType, MclBnFr, MclBnG1, MclBnG2};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SIZES: [usize; 4] = [16, 64, 256, 1024];
+
+fn scalars(n: usize) -> Vec<MclBnFr> {
+    (0..n).map(|_| MclBnFr::new_random().unwrap()).collect()
+}
+
+fn multi_exp_g1(c: &mut Criterion) {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    let mut group = c.benchmark_group("multi_exp_g1");
+    for n in SIZES.iter() {
+        let points = (0..*n)
+            .map(|i| MclBnG1::hash_and_map_to(&i.to_le_bytes()).unwrap())
+            .collect::<Vec<_>>();
+        let scalars = scalars(*n);
+
+        group.bench_with_input(BenchmarkId::new("naive", n), n, |b, _| {
+            b.iter(|| {
+                points
+                    .iter()
+                    .zip(scalars.iter())
+                    .fold(MclBnG1::new(), |acc, (p, s)| acc + p.clone() * s.clone())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("multi_exp", n), n, |b, _| {
+            b.iter(|| MclBnG1::multi_exp(&points, &scalars).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn multi_exp_g2(c: &mut Criterion) {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    let mut group = c.benchmark_group("multi_exp_g2");
+    for n in SIZES.iter() {
+        let points = (0..*n)
+            .map(|i| MclBnG2::hash_and_map_to(&i.to_le_bytes()).unwrap())
+            .collect::<Vec<_>>();
+        let scalars = scalars(*n);
+
+        group.bench_with_input(BenchmarkId::new("naive", n), n, |b, _| {
+            b.iter(|| {
+                points
+                    .iter()
+                    .zip(scalars.iter())
+                    .fold(MclBnG2::new(), |acc, (p, s)| acc + p.clone() * s.clone())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("multi_exp", n), n, |b, _| {
+            b.iter(|| MclBnG2::multi_exp(&points, &scalars).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, multi_exp_g1, multi_exp_g2);
+criterion_main!(benches);
@@ -12,6 +12,15 @@ fn fail_on_empty_directory(name: &str) {
     }
 }
 
+fn fail_on_old_mcl() {
+    // Needed by `to_uncompressed` and `from_uncompressed`
+    let op = fs::read_to_string("mcl/include/mcl/op.hpp").unwrap();
+    if !op.contains("IoEcAffineSerialize") {
+        println!("The `mcl` submodule predates `IoEcAffineSerialize`, update it");
+        panic!();
+    }
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=mcl/");
@@ -19,6 +28,7 @@ fn main() {
 
     fail_on_empty_directory("mcl");
     fail_on_empty_directory("bls");
+    fail_on_old_mcl();
 
     println!("cargo:rustc-link-lib=static=bls384_256");
     println!("cargo:rustc-link-lib=static=mcl");
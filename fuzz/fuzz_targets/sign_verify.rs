@@ -0,0 +1,37 @@
+#![no_main]
+
+use bls_sys::{pairing_product_is_one, BlsSecretKey, MclBnFr, MclBnG1, MclBnG2};
+use libfuzzer_sys::fuzz_target;
+
+// Signs with a key taken from the first bytes of the input and cross-checks `sign` and `verify`
+// against the same computation done with the pairing primitives.
+fuzz_target!(|data: &[u8]| {
+    bls_sys_fuzz::init();
+
+    if data.len() < 32 {
+        return;
+    }
+    let (key, msg) = data.split_at(32);
+
+    let mut sk = BlsSecretKey::new();
+    sk.set_little_endian_mod(key);
+    let pk = sk.to_public_key();
+    let sig = sk.sign(msg);
+
+    // sig = H(msg) * sk
+    let h = MclBnG1::hash_and_map_to(msg).unwrap();
+    assert_eq!(MclBnG1::from(sig.clone()), h.clone() * MclBnFr::from(sk.clone()));
+
+    // e(sig, Q) == e(H(msg), pk)
+    let q = MclBnG2::from(bls_sys::BlsPublicKey::generator());
+    let pairs = [(-MclBnG1::from(sig.clone()), q), (h, MclBnG2::from(pk.clone()))];
+    assert!(pairing_product_is_one(&pairs));
+
+    // The identity public key is rejected, any other key must accept its own signature only
+    assert_eq!(!pk.is_zero(), sig.verify(&pk, msg));
+    if !pk.is_zero() {
+        let mut other = msg.to_vec();
+        other.push(0);
+        assert!(!sig.verify(&pk, &other));
+    }
+});
@@ -0,0 +1,68 @@
+//! Shared checks for the fuzz targets in `fuzz_targets/`.
+
+use bls_sys::{bls_init, BlsId, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+pub fn init() {
+    INIT.call_once(|| bls_init(CurveType::Bls12CurveFp381).unwrap());
+}
+
+macro_rules! impl_checks {
+    ($type:ty, $deserialize:ident, $parse:ident) => {
+        /// Deserializes arbitrary bytes, checking that anything accepted serializes back to the
+        /// bytes it was read from.
+        pub fn $deserialize(data: &[u8]) {
+            init();
+
+            let mut value = <$type>::new();
+            if let Ok(size) = value.deserialize(data) {
+                assert!(size <= data.len());
+
+                let mut buf = [0u8; 128];
+                let out_size = value.serialize(&mut buf).unwrap();
+                assert_eq!(&data[..size], &buf[..out_size]);
+                assert_eq!(value, <$type>::try_from(&buf[..out_size]).unwrap());
+            }
+        }
+
+        /// Parses an arbitrary string with every string entry point, checking that anything
+        /// accepted survives a round-trip through the matching export.
+        pub fn $parse(data: &[u8]) {
+            init();
+
+            let s = match std::str::from_utf8(data) {
+                Ok(s) => s,
+                Err(_) => return,
+            };
+
+            let mut value = <$type>::new();
+            if value.set_dec_str(s).is_ok() {
+                let mut parsed = <$type>::new();
+                parsed.set_dec_str(&value.get_dec_str().unwrap()).unwrap();
+                assert_eq!(value, parsed);
+            }
+
+            let mut value = <$type>::new();
+            if value.set_hex_str(s).is_ok() {
+                let mut parsed = <$type>::new();
+                parsed.set_hex_str(&value.get_hex_str().unwrap()).unwrap();
+                assert_eq!(value, parsed);
+            }
+
+            if let Ok(value) = <$type>::from_str(s) {
+                let mut buf = [0u8; 128];
+                let size = value.serialize(&mut buf).unwrap();
+                assert_eq!(value, <$type>::try_from(&buf[..size]).unwrap());
+            }
+        }
+    };
+}
+
+impl_checks!(BlsId, deserialize_id, parse_id);
+impl_checks!(BlsSecretKey, deserialize_secret_key, parse_secret_key);
+impl_checks!(BlsPublicKey, deserialize_public_key, parse_public_key);
+impl_checks!(BlsSignature, deserialize_signature, parse_signature);
@@ -0,0 +1,135 @@
+//! Aggregate signatures of a single message that track which members of a known committee
+//! signed.
+//!
+//! Verification sums the public keys of the participating members, so committee keys must come
+//! with a proof of possession to rule out rogue key attacks.
+
+use crate::{BlsPublicKey, BlsSignature};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateSignature {
+    signature: BlsSignature,
+    committee_size: usize,
+    // One bit per member, least significant bit first
+    bitfield: Vec<u8>,
+}
+
+impl AggregateSignature {
+    /// Creates an empty aggregate for a committee of `committee_size` members.
+    pub fn new(committee_size: usize) -> Self {
+        AggregateSignature {
+            signature: BlsSignature::new(),
+            committee_size,
+            bitfield: vec![0; bitfield_len(committee_size)],
+        }
+    }
+
+    /// Reassembles an aggregate from its signature and bitfield, as received over the wire.
+    pub fn from_parts(
+        signature: BlsSignature,
+        bitfield: &[u8],
+        committee_size: usize,
+    ) -> Result<Self, ()> {
+        let aggregate = AggregateSignature {
+            signature,
+            committee_size,
+            bitfield: bitfield.to_vec(),
+        };
+
+        // Padding bits past the last member must be clear
+        let valid = bitfield.len() == bitfield_len(committee_size)
+            && (committee_size..bitfield.len() * 8).all(|i| !aggregate.get(i));
+        if valid {
+            Ok(aggregate)
+        } else {
+            Err(())
+        }
+    }
+
+    pub fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+
+    pub fn bitfield(&self) -> &[u8] {
+        &self.bitfield
+    }
+
+    pub fn committee_size(&self) -> usize {
+        self.committee_size
+    }
+
+    pub fn has_signed(&self, member: usize) -> bool {
+        member < self.committee_size && self.get(member)
+    }
+
+    /// Indices of the members that signed, in ascending order.
+    pub fn signers(&self) -> Vec<usize> {
+        (0..self.committee_size).filter(|i| self.get(*i)).collect()
+    }
+
+    pub fn num_signers(&self) -> usize {
+        self.bitfield.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Adds the signature of `member`, failing if it is out of range or already included.
+    pub fn add(&mut self, member: usize, signature: &BlsSignature) -> Result<(), ()> {
+        if member >= self.committee_size || self.get(member) {
+            return Err(());
+        }
+
+        self.signature += signature;
+        self.bitfield[member / 8] |= 1 << (member % 8);
+
+        Ok(())
+    }
+
+    /// Merges another aggregate over the same committee, failing if any member signed both.
+    pub fn merge(&mut self, other: &AggregateSignature) -> Result<(), ()> {
+        if self.committee_size != other.committee_size
+            || self
+                .bitfield
+                .iter()
+                .zip(other.bitfield.iter())
+                .any(|(a, b)| a & b != 0)
+        {
+            return Err(());
+        }
+
+        self.signature += &other.signature;
+        for (a, b) in self.bitfield.iter_mut().zip(other.bitfield.iter()) {
+            *a |= b;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the aggregate against the sum of the public keys of the members that signed.
+    ///
+    /// `committee` lists the public keys of all members in bitfield order.
+    pub fn verify(&self, committee: &[BlsPublicKey], msg: &[u8]) -> bool {
+        if committee.len() != self.committee_size || self.num_signers() == 0 {
+            return false;
+        }
+
+        let public_key = committee
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.get(*i))
+            .map(|(_, pk)| pk)
+            .sum::<BlsPublicKey>();
+
+        self.signature.verify(&public_key, msg)
+    }
+
+    fn get(&self, member: usize) -> bool {
+        self.bitfield[member / 8] & (1 << (member % 8)) != 0
+    }
+}
+
+// `usize::div_ceil` needs Rust 1.73
+#[allow(clippy::manual_div_ceil)]
+fn bitfield_len(committee_size: usize) -> usize {
+    (committee_size + 7) / 8
+}
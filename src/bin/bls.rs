@@ -0,0 +1,234 @@
+//! Command-line tool for everyday key management and signing tasks.
+//!
+//! Keys, signatures and shares are passed around as hex of their serialized bytes. Any such
+//! argument can also be given as `@path` to read the hex from a file. Messages are taken
+//! verbatim, as hex when prefixed with `0x`, or as raw file contents when given as `@path`.
+
+use bls_sys::{bls_init, BlsId, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType};
+use rand::rngs::OsRng;
+use std::convert::TryFrom;
+use std::fs;
+use std::process;
+
+const USAGE: &str = "Usage: bls <command> [args...] [--out <file>]
+
+Commands:
+    keygen                                  Generate a random secret key
+    pubkey <secret-key>                     Print the public key of a secret key
+    sign <secret-key> <message>             Sign a message
+    verify <public-key> <signature> <message>
+                                            Verify a signature, exits with 1 if invalid
+    aggregate-sigs <signature>...           Aggregate signatures
+    aggregate-pks <public-key>...           Aggregate public keys
+    share <secret-key> <threshold> <id>...  Split a secret key into `id:share` pairs
+    recover [--secret-key | --public-key] <id:share>...
+                                            Recover a signature (or key) from shares";
+
+const SERIALIZED_BUF_SIZE: usize = 256;
+
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<String>>();
+
+    let out = match args.iter().position(|arg| arg == "--out") {
+        Some(pos) if pos + 1 < args.len() => {
+            let path = args.remove(pos + 1);
+            args.remove(pos);
+            Some(path)
+        }
+        Some(_) => fail("--out requires a file path"),
+        None => None,
+    };
+
+    bls_init(CurveType::Bls12CurveFp381).unwrap_or_else(|_| fail("unable to initialise BLS lib"));
+
+    let (output, success) = match run(&args) {
+        Ok(result) => result,
+        Err(err) => fail(&err),
+    };
+
+    match out {
+        Some(path) => fs::write(&path, format!("{}\n", output))
+            .unwrap_or_else(|err| fail(&format!("unable to write {}: {}", path, err))),
+        None => println!("{}", output),
+    }
+
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn fail(msg: &str) -> ! {
+    eprintln!("error: {}\n\n{}", msg, USAGE);
+    process::exit(2);
+}
+
+/// Runs a command, returning its output and whether it succeeded.
+fn run(args: &[String]) -> Result<(String, bool), String> {
+    let (command, args) = args
+        .split_first()
+        .ok_or_else(|| "no command given".to_string())?;
+    let args = args.iter().map(String::as_str).collect::<Vec<&str>>();
+
+    let output = match (command.as_str(), args.as_slice()) {
+        ("keygen", []) => {
+            let sk = BlsSecretKey::new_random()
+                .map_err(|_| "unable to obtain system randomness".to_string())?;
+            to_hex(|buf| sk.serialize(buf))?
+        }
+        ("pubkey", [sk]) => {
+            let pk = parse_secret_key(sk)?.to_public_key();
+            to_hex(|buf| pk.serialize(buf))?
+        }
+        ("sign", [sk, msg]) => {
+            let sig = parse_secret_key(sk)?.sign(&read_message(msg)?);
+            to_hex(|buf| sig.serialize(buf))?
+        }
+        ("verify", [pk, sig, msg]) => {
+            let valid = parse_signature(sig)?.verify(&parse_public_key(pk)?, &read_message(msg)?);
+            let output = if valid { "valid" } else { "invalid" };
+
+            return Ok((output.to_string(), valid));
+        }
+        ("aggregate-sigs", sigs) if !sigs.is_empty() => {
+            let mut agg = BlsSignature::new();
+            for sig in sigs {
+                agg += parse_signature(sig)?;
+            }
+            to_hex(|buf| agg.serialize(buf))?
+        }
+        ("aggregate-pks", pks) if !pks.is_empty() => {
+            let mut agg = BlsPublicKey::new();
+            for pk in pks {
+                agg += parse_public_key(pk)?;
+            }
+            to_hex(|buf| agg.serialize(buf))?
+        }
+        ("share", [sk, threshold, ids @ ..]) if !ids.is_empty() => share(sk, threshold, ids)?,
+        ("recover", ["--secret-key", shares @ ..]) if !shares.is_empty() => {
+            let (ids, shares) = parse_shares(shares, parse_secret_key)?;
+            let sk = BlsSecretKey::recover(&shares, &ids, shares.len())
+                .map_err(|_| "unable to recover secret key".to_string())?;
+            to_hex(|buf| sk.serialize(buf))?
+        }
+        ("recover", ["--public-key", shares @ ..]) if !shares.is_empty() => {
+            let (ids, shares) = parse_shares(shares, parse_public_key)?;
+            let pk = BlsPublicKey::recover(&shares, &ids, shares.len())
+                .map_err(|_| "unable to recover public key".to_string())?;
+            to_hex(|buf| pk.serialize(buf))?
+        }
+        ("recover", shares) if !shares.is_empty() => {
+            let (ids, shares) = parse_shares(shares, parse_signature)?;
+            let sig = BlsSignature::recover(&shares, &ids, shares.len())
+                .map_err(|_| "unable to recover signature".to_string())?;
+            to_hex(|buf| sig.serialize(buf))?
+        }
+        _ => return Err(format!("invalid arguments for `{}`", command)),
+    };
+
+    Ok((output, true))
+}
+
+fn share(sk: &str, threshold: &str, ids: &[&str]) -> Result<String, String> {
+    let threshold = threshold
+        .parse::<usize>()
+        .map_err(|_| format!("invalid threshold: {}", threshold))?;
+    if threshold == 0 || threshold > ids.len() {
+        return Err(format!(
+            "threshold must be between 1 and the number of ids ({})",
+            ids.len()
+        ));
+    }
+
+    // Master secret key polynomial of degree `threshold - 1` with the shared key as the free term
+    let msk = parse_secret_key(sk)?.new_master_keys(threshold, &mut OsRng);
+
+    let mut lines = Vec::with_capacity(ids.len());
+    for id in ids {
+        let share = BlsSecretKey::new_share(&msk[0], threshold, &parse_id(id)?)
+            .map_err(|_| format!("unable to create share for id {}", id))?;
+        lines.push(format!("{}:{}", id, to_hex(|buf| share.serialize(buf))?));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+fn to_hex<F>(serialize: F) -> Result<String, String>
+where
+    F: FnOnce(&mut [u8]) -> Result<usize, ()>,
+{
+    let mut buf = [0u8; SERIALIZED_BUF_SIZE];
+    let size = serialize(&mut buf).map_err(|_| "unable to serialize".to_string())?;
+
+    Ok(hex::encode(&buf[..size]))
+}
+
+/// Reads hex from the argument itself or, for `@path`, from the file.
+fn read_hex(arg: &str) -> Result<Vec<u8>, String> {
+    let hex_str = match arg.strip_prefix('@') {
+        Some(path) => {
+            fs::read_to_string(path).map_err(|err| format!("unable to read {}: {}", path, err))?
+        }
+        None => arg.to_string(),
+    };
+    let hex_str = hex_str.trim();
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+
+    hex::decode(hex_str).map_err(|_| format!("invalid hex: {}", arg))
+}
+
+fn read_message(arg: &str) -> Result<Vec<u8>, String> {
+    if let Some(path) = arg.strip_prefix('@') {
+        fs::read(path).map_err(|err| format!("unable to read {}: {}", path, err))
+    } else if let Some(hex_str) = arg.strip_prefix("0x") {
+        hex::decode(hex_str).map_err(|_| format!("invalid hex message: {}", arg))
+    } else {
+        Ok(arg.as_bytes().to_vec())
+    }
+}
+
+macro_rules! parse_fn {
+    ($fn_name:ident, $type:ty, $name:expr) => {
+        fn $fn_name(arg: &str) -> Result<$type, String> {
+            let buf = read_hex(arg)?;
+
+            <$type>::try_from(buf.as_slice()).map_err(|_| format!("invalid {}: {}", $name, arg))
+        }
+    };
+}
+
+parse_fn!(parse_secret_key, BlsSecretKey, "secret key");
+parse_fn!(parse_public_key, BlsPublicKey, "public key");
+parse_fn!(parse_signature, BlsSignature, "signature");
+
+fn parse_id(arg: &str) -> Result<BlsId, String> {
+    let mut id = BlsId::new();
+    id.set_dec_str(arg)
+        .map_err(|_| format!("invalid id: {}", arg))?;
+
+    if id == BlsId::new() {
+        return Err("id must be non-zero".to_string());
+    }
+
+    Ok(id)
+}
+
+fn parse_shares<T, F>(args: &[&str], parse: F) -> Result<(Vec<BlsId>, Vec<T>), String>
+where
+    F: Fn(&str) -> Result<T, String>,
+{
+    let mut ids = Vec::with_capacity(args.len());
+    let mut shares = Vec::with_capacity(args.len());
+
+    for arg in args {
+        let mut parts = arg.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(id), Some(share)) => {
+                ids.push(parse_id(id)?);
+                shares.push(parse(share)?);
+            }
+            _ => return Err(format!("expected `id:share`, got {}", arg)),
+        }
+    }
+
+    Ok((ids, shares))
+}
@@ -0,0 +1,113 @@
+//! Hierarchical secret key derivation as specified by
+//! [EIP-2333](https://eips.ethereum.org/EIPS/eip-2333).
+//!
+//! Derived keys are reduced modulo the group order of the curve selected with
+//! `bls_init`, so the results match the EIP only for `CurveType::Bls12CurveFp381`.
+
+use crate::BlsSecretKey;
+use alloc::vec;
+use alloc::vec::Vec;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+const LAMPORT_CHUNKS: usize = 255;
+const DIGEST_SIZE: usize = 32;
+const OKM_SIZE: usize = 48;
+
+impl BlsSecretKey {
+    /// Derives the master secret key from a seed of at least 32 bytes.
+    pub fn derive_master(seed: &[u8]) -> Result<Self, ()> {
+        if seed.len() < 32 {
+            return Err(());
+        }
+
+        Ok(hkdf_mod_r(seed))
+    }
+
+    /// Derives the child secret key at `index`.
+    pub fn derive_child(&self, index: u32) -> Self {
+        hkdf_mod_r(&parent_sk_to_lamport_pk(self, index))
+    }
+
+    /// Derives the secret key at `path` (e.g. `m/12381/3600/0/0/0`) from a seed.
+    pub fn derive_path(seed: &[u8], path: &str) -> Result<Self, ()> {
+        let indices = parse_path(path)?;
+        let master = BlsSecretKey::derive_master(seed)?;
+
+        Ok(indices
+            .into_iter()
+            .fold(master, |sk, index| sk.derive_child(index)))
+    }
+}
+
+/// Parses a derivation path of the form `m/12381/3600/0/0/0` into child indices.
+pub fn parse_path(path: &str) -> Result<Vec<u32>, ()> {
+    let mut components = path.split('/');
+
+    if components.next() != Some("m") {
+        return Err(());
+    }
+
+    components
+        .map(|c| {
+            if c.is_empty() || !c.bytes().all(|b| b.is_ascii_digit()) {
+                Err(())
+            } else {
+                c.parse::<u32>().map_err(|_| ())
+            }
+        })
+        .collect()
+}
+
+fn hkdf_mod_r(ikm: &[u8]) -> BlsSecretKey {
+    let mut ikm = ikm.to_vec();
+    ikm.push(0);
+
+    let mut salt = Sha256::digest(KEYGEN_SALT);
+    let mut sk = BlsSecretKey::new();
+
+    while sk == BlsSecretKey::new() {
+        let mut okm = [0u8; OKM_SIZE];
+        Hkdf::<Sha256>::new(Some(&salt), &ikm)
+            .expand(&[0, OKM_SIZE as u8], &mut okm)
+            .expect("48 bytes is a valid HKDF output length");
+
+        // OKM is a big-endian integer
+        okm.reverse();
+        sk.set_little_endian_mod(&okm);
+
+        salt = Sha256::digest(salt);
+    }
+
+    sk
+}
+
+fn ikm_to_lamport_sk(ikm: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut okm = vec![0u8; LAMPORT_CHUNKS * DIGEST_SIZE];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .expect("255 digests is the maximum valid HKDF output length");
+
+    okm
+}
+
+fn parent_sk_to_lamport_pk(parent: &BlsSecretKey, index: u32) -> Vec<u8> {
+    let salt = index.to_be_bytes();
+    let ikm = parent.to_be_bytes();
+    let not_ikm = ikm.iter().map(|b| !b).collect::<Vec<u8>>();
+
+    let mut hasher = Sha256::new();
+    for lamport_sk in [
+        ikm_to_lamport_sk(&ikm, &salt),
+        ikm_to_lamport_sk(&not_ikm, &salt),
+    ]
+    .iter()
+    {
+        for chunk in lamport_sk.chunks(DIGEST_SIZE) {
+            hasher.update(Sha256::digest(chunk));
+        }
+    }
+
+    hasher.finalize().to_vec()
+}
@@ -0,0 +1,360 @@
+//! Password-encrypted secret key storage in the
+//! [EIP-2335](https://eips.ethereum.org/EIPS/eip-2335) JSON keystore format.
+//!
+//! As EIP-2335 requires, `pubkey` holds `sk * g1` for the standard BLS12-381 generator `g1`, in the
+//! compressed ZCash encoding. It is not the G2 key returned by `BlsSecretKey::to_public_key`.
+
+use crate::{BlsSecretKey, IoMode, MclBnG1, FP_SIZE, ZCASH_COMPRESSED_FLAG, ZCASH_SIGN_FLAG};
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+const VERSION: u32 = 4;
+const DKLEN: u32 = 32;
+const SALT_SIZE: usize = 32;
+const IV_SIZE: usize = 16;
+
+// Enough for the parameters recommended by EIP-2335, which take 256 MiB
+const MAX_SCRYPT_MEMORY: u64 = 1 << 28;
+const MAX_SCRYPT_P: u32 = 16;
+
+// Four times the rounds recommended by EIP-2335
+const MAX_PBKDF2_C: u32 = 1 << 20;
+
+// The standard BLS12-381 generator of G1
+const G1_GENERATOR: &str = "1 17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb 08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+
+const SCRYPT: &str = "scrypt";
+const PBKDF2: &str = "pbkdf2";
+const PBKDF2_PRF: &str = "hmac-sha256";
+const CHECKSUM: &str = "sha256";
+const CIPHER: &str = "aes-128-ctr";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeystoreError {
+    InvalidJson,
+    UnsupportedVersion,
+    UnsupportedFunction,
+    InvalidParams,
+    InvalidPassword,
+    InvalidSecretKey,
+    PublicKeyMismatch,
+    UnsupportedCurve,
+}
+
+/// Key derivation function used to turn the password into the decryption key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    Scrypt { n: u32, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl Kdf {
+    /// Scrypt with the parameters recommended by EIP-2335.
+    pub fn scrypt() -> Self {
+        Kdf::Scrypt {
+            n: 262_144,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    /// PBKDF2 with the parameters recommended by EIP-2335.
+    pub fn pbkdf2() -> Self {
+        Kdf::Pbkdf2 { c: 262_144 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub crypto: Crypto,
+    #[serde(default)]
+    pub description: String,
+    pub pubkey: String,
+    pub path: String,
+    pub uuid: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Crypto {
+    pub kdf: Module<KdfParams>,
+    pub checksum: Module<EmptyParams>,
+    pub cipher: Module<CipherParams>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Module<P> {
+    pub function: String,
+    pub params: P,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KdfParams {
+    Scrypt {
+        dklen: u32,
+        n: u32,
+        p: u32,
+        r: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        dklen: u32,
+        c: u32,
+        prf: String,
+        salt: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmptyParams {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    pub iv: String,
+}
+
+impl Keystore {
+    /// Encrypts `sk` with `password`, recording the derivation `path` it was created at.
+    pub fn encrypt(
+        sk: &BlsSecretKey,
+        password: &str,
+        path: &str,
+        kdf: Kdf,
+    ) -> Result<Self, KeystoreError> {
+        let mut salt = [0u8; SALT_SIZE];
+        let mut iv = [0u8; IV_SIZE];
+        let mut uuid = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut iv);
+        OsRng.fill_bytes(&mut uuid);
+
+        let kdf = match kdf {
+            Kdf::Scrypt { n, r, p } => Module {
+                function: SCRYPT.to_string(),
+                params: KdfParams::Scrypt {
+                    dklen: DKLEN,
+                    n,
+                    p,
+                    r,
+                    salt: hex::encode(salt),
+                },
+                message: String::new(),
+            },
+            Kdf::Pbkdf2 { c } => Module {
+                function: PBKDF2.to_string(),
+                params: KdfParams::Pbkdf2 {
+                    dklen: DKLEN,
+                    c,
+                    prf: PBKDF2_PRF.to_string(),
+                    salt: hex::encode(salt),
+                },
+                message: String::new(),
+            },
+        };
+
+        let key = derive_key(&kdf, password)?;
+
+        let mut secret = sk.to_be_bytes();
+        apply_cipher(&key, &iv, &mut secret);
+
+        Ok(Keystore {
+            crypto: Crypto {
+                kdf,
+                checksum: Module {
+                    function: CHECKSUM.to_string(),
+                    params: EmptyParams {},
+                    message: hex::encode(checksum(&key, &secret)),
+                },
+                cipher: Module {
+                    function: CIPHER.to_string(),
+                    params: CipherParams {
+                        iv: hex::encode(iv),
+                    },
+                    message: hex::encode(secret),
+                },
+            },
+            description: String::new(),
+            pubkey: hex::encode(public_key(sk)?),
+            path: path.to_string(),
+            uuid: format_uuid(uuid),
+            version: VERSION,
+        })
+    }
+
+    /// Decrypts the secret key, failing with `InvalidPassword` if the checksum doesn't match and
+    /// with `PublicKeyMismatch` if the key doesn't belong to `pubkey`.
+    pub fn decrypt(&self, password: &str) -> Result<BlsSecretKey, KeystoreError> {
+        if self.version != VERSION {
+            return Err(KeystoreError::UnsupportedVersion);
+        }
+
+        let crypto = &self.crypto;
+        if crypto.checksum.function != CHECKSUM || crypto.cipher.function != CIPHER {
+            return Err(KeystoreError::UnsupportedFunction);
+        }
+
+        let key = derive_key(&crypto.kdf, password)?;
+
+        let mut secret =
+            hex::decode(&crypto.cipher.message).map_err(|_| KeystoreError::InvalidParams)?;
+        let expected_checksum =
+            hex::decode(&crypto.checksum.message).map_err(|_| KeystoreError::InvalidParams)?;
+        if checksum(&key, &secret)[..] != expected_checksum[..] {
+            return Err(KeystoreError::InvalidPassword);
+        }
+
+        let iv = hex::decode(&crypto.cipher.params.iv).map_err(|_| KeystoreError::InvalidParams)?;
+        if iv.len() != IV_SIZE {
+            return Err(KeystoreError::InvalidParams);
+        }
+        apply_cipher(&key, &iv, &mut secret);
+
+        let mut sk = BlsSecretKey::new();
+        sk.set_hex_str(&hex::encode(&secret))
+            .map_err(|_| KeystoreError::InvalidSecretKey)?;
+
+        let pubkey = hex::decode(&self.pubkey).map_err(|_| KeystoreError::InvalidParams)?;
+        if pubkey != public_key(&sk)? {
+            return Err(KeystoreError::PublicKeyMismatch);
+        }
+
+        Ok(sk)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        serde_json::from_str(json).map_err(|_| KeystoreError::InvalidJson)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("keystore is always serializable")
+    }
+}
+
+/// Applies the NFKD normalization and control code stripping required by EIP-2335.
+fn process_password(password: &str) -> Vec<u8> {
+    password
+        .nfkd()
+        .filter(|c| !matches!(*c as u32, 0x00..=0x1f | 0x7f..=0x9f))
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn derive_key(kdf: &Module<KdfParams>, password: &str) -> Result<Vec<u8>, KeystoreError> {
+    let password = process_password(password);
+
+    match (kdf.function.as_str(), &kdf.params) {
+        (
+            SCRYPT,
+            KdfParams::Scrypt {
+                dklen,
+                n,
+                p,
+                r,
+                salt,
+            },
+        ) => {
+            // Scrypt needs 128 * n * r bytes of memory
+            if *dklen != DKLEN
+                || !n.is_power_of_two()
+                || u64::from(*n) * u64::from(*r) > MAX_SCRYPT_MEMORY / 128
+                || *p > MAX_SCRYPT_P
+            {
+                return Err(KeystoreError::InvalidParams);
+            }
+
+            let salt = hex::decode(salt).map_err(|_| KeystoreError::InvalidParams)?;
+            let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen as usize)
+                .map_err(|_| KeystoreError::InvalidParams)?;
+
+            let mut key = vec![0u8; *dklen as usize];
+            scrypt::scrypt(&password, &salt, &params, &mut key)
+                .map_err(|_| KeystoreError::InvalidParams)?;
+
+            Ok(key)
+        }
+        (
+            PBKDF2,
+            KdfParams::Pbkdf2 {
+                dklen,
+                c,
+                prf,
+                salt,
+            },
+        ) => {
+            if *dklen != DKLEN || *c > MAX_PBKDF2_C || prf != PBKDF2_PRF {
+                return Err(KeystoreError::InvalidParams);
+            }
+
+            let salt = hex::decode(salt).map_err(|_| KeystoreError::InvalidParams)?;
+
+            let mut key = vec![0u8; *dklen as usize];
+            pbkdf2::pbkdf2_hmac::<Sha256>(&password, &salt, *c, &mut key);
+
+            Ok(key)
+        }
+        (SCRYPT, _) | (PBKDF2, _) => Err(KeystoreError::InvalidParams),
+        _ => Err(KeystoreError::UnsupportedFunction),
+    }
+}
+
+/// The EIP-2335 public key of `sk`, failing unless the curve is BLS12-381.
+fn public_key(sk: &BlsSecretKey) -> Result<Vec<u8>, KeystoreError> {
+    if sk.0.is_zero() {
+        return Err(KeystoreError::InvalidSecretKey);
+    }
+
+    let mut g1 = MclBnG1::new();
+    g1.set_str(G1_GENERATOR, IoMode::Hex)
+        .map_err(|_| KeystoreError::UnsupportedCurve)?;
+
+    let pk = g1 * sk.0.clone();
+    let coords = pk.canonical_bytes();
+    let neg_coords = (-pk).canonical_bytes();
+
+    let mut buf = coords[..FP_SIZE].to_vec();
+    buf[0] |= ZCASH_COMPRESSED_FLAG;
+    // Set when y is the larger of y and -y
+    if coords[FP_SIZE..] > neg_coords[FP_SIZE..] {
+        buf[0] |= ZCASH_SIGN_FLAG;
+    }
+
+    Ok(buf)
+}
+
+fn checksum(key: &[u8], cipher_message: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(&key[16..32]);
+    hasher.update(cipher_message);
+
+    hasher.finalize().to_vec()
+}
+
+fn apply_cipher(key: &[u8], iv: &[u8], buf: &mut [u8]) {
+    let mut cipher = Aes128Ctr::new(key[..16].into(), iv.into());
+    cipher.apply_keystream(buf);
+}
+
+fn format_uuid(mut bytes: [u8; 16]) -> String {
+    // Random (version 4) UUID
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
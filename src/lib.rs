@@ -1,9 +1,40 @@
-use libc::{c_int, size_t, uint64_t, uint8_t};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt::{Error, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::str::FromStr;
+use libc::{c_char, c_int, size_t, uint64_t, uint8_t};
+#[cfg(feature = "std")]
+use libc::{c_uint, c_void};
 use paste;
-use std::ffi::{CStr, CString};
-use std::fmt::{Error, Formatter};
-use std::ops::{Add, AddAssign, Sub, SubAssign};
-use std::os::raw::c_char;
+#[cfg(feature = "std")]
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+use subtle::{Choice, ConstantTimeEq};
+
+pub mod aggregate;
+mod derive;
+#[cfg(feature = "std")]
+pub mod keystore;
+#[cfg(feature = "std")]
+pub mod mnemonic;
+#[cfg(feature = "pippenger")]
+mod multi_exp;
+pub mod weighted;
+
+pub use derive::parse_path;
 
 pub const MCLBN_FR_UNIT_SIZE: c_int = 4;
 pub const MCLBN_FP_UNIT_SIZE: c_int = 6;
@@ -11,8 +42,10 @@ pub const MCLBN_FP_UNIT_SIZE: c_int = 6;
 #[link(name = "bls384_256")]
 extern "C" {
     fn blsInit(curve: c_int, compiled_var: c_int) -> c_int;
+    fn blsSetETHserialization(eth_serialization: c_int);
 
     fn blsIdSetInt(id: *mut BlsId, x: c_int);
+    fn blsIdSetLittleEndian(id: *mut BlsId, buf: *const uint8_t, buf_size: size_t) -> c_int;
     fn blsIdSerialize(buf: *mut uint8_t, buf_size: size_t, id: *const BlsId) -> size_t;
     fn blsIdDeserialize(id: *mut BlsId, buf: *const uint8_t, buf_size: size_t) -> size_t;
     fn blsIdIsEqual(lhs: *const BlsId, rhs: *const BlsId) -> size_t;
@@ -31,7 +64,6 @@ extern "C" {
         buf: *const uint8_t,
         buf_size: size_t,
     ) -> size_t;
-    fn blsSecretKeyIsEqual(lhs: *const BlsSecretKey, rhs: *const BlsSecretKey) -> size_t;
     fn blsSecretKeySetDecStr(sk: *mut BlsSecretKey, buf: *const c_char, buf_size: size_t)
         -> size_t;
     fn blsSecretKeySetHexStr(sk: *mut BlsSecretKey, buf: *const c_char, buf_size: size_t)
@@ -60,6 +92,8 @@ extern "C" {
     fn blsSecretKeySetByCSPRNG(sk: *mut BlsSecretKey) -> c_int;
     fn blsSecretKeyAdd(this_sk: *mut BlsSecretKey, other: *const BlsSecretKey);
     fn blsSecretKeySub(this_sk: *mut BlsSecretKey, other: *const BlsSecretKey);
+    fn blsSecretKeyNeg(this_sk: *mut BlsSecretKey);
+    fn blsSecretKeyMul(this_sk: *mut BlsSecretKey, other: *const BlsSecretKey);
     fn blsSecretKeyShare(
         sk: *mut BlsSecretKey,
         msk: *const BlsSecretKey,
@@ -100,6 +134,11 @@ extern "C" {
     ) -> size_t;
     fn blsPublicKeyAdd(this_pk: *mut BlsPublicKey, other: *const BlsPublicKey);
     fn blsPublicKeySub(this_pk: *mut BlsPublicKey, other: *const BlsPublicKey);
+    fn blsPublicKeyNeg(this_pk: *mut BlsPublicKey);
+    fn blsPublicKeyIsZero(pk: *const BlsPublicKey) -> c_int;
+    fn blsPublicKeyIsValidOrder(pk: *const BlsPublicKey) -> c_int;
+    fn blsPublicKeyVerifyOrder(do_verify: c_int);
+    fn blsPublicKeyMul(this_pk: *mut BlsPublicKey, scalar: *const BlsSecretKey);
     fn blsPublicKeyShare(
         pk: *mut BlsPublicKey,
         mpk: *const BlsPublicKey,
@@ -140,6 +179,11 @@ extern "C" {
     ) -> size_t;
     fn blsSignatureAdd(this_sig: *mut BlsSignature, other: *const BlsSignature);
     fn blsSignatureSub(this_sig: *mut BlsSignature, other: *const BlsSignature);
+    fn blsSignatureNeg(this_sig: *mut BlsSignature);
+    fn blsSignatureIsZero(sig: *const BlsSignature) -> c_int;
+    fn blsSignatureIsValidOrder(sig: *const BlsSignature) -> c_int;
+    fn blsSignatureVerifyOrder(do_verify: c_int);
+    fn blsSignatureMul(this_sig: *mut BlsSignature, scalar: *const BlsSecretKey);
     fn blsSignatureRecover(
         sig: *mut BlsSignature,
         sig_vec: *const BlsSignature,
@@ -148,16 +192,174 @@ extern "C" {
     ) -> c_int;
 
     fn blsSign(sig: *mut BlsSignature, sk: *const BlsSecretKey, msg: *const uint8_t, size: size_t);
-    fn blsVerify(
-        sig: *const BlsSignature,
-        pk: *const BlsPublicKey,
-        msg: *const uint8_t,
-        size: size_t,
+    fn blsGetGeneratorOfPublicKey(pk: *mut BlsPublicKey);
+    fn blsSetGeneratorOfPublicKey(pk: *const BlsPublicKey) -> c_int;
+}
+
+#[link(name = "mclbn384_256")]
+extern "C" {
+    fn mclBnFr_setInt(y: *mut MclBnFr, x: i64);
+    fn mclBnFr_setStr(
+        x: *mut MclBnFr,
+        buf: *const c_char,
+        buf_size: size_t,
+        io_mode: c_int,
+    ) -> c_int;
+    fn mclBnFr_getStr(
+        buf: *mut c_char,
+        max_buf_size: size_t,
+        x: *const MclBnFr,
+        io_mode: c_int,
+    ) -> size_t;
+    fn mclBnFr_setByCSPRNG(x: *mut MclBnFr) -> c_int;
+    fn mclBnFr_setHashOf(x: *mut MclBnFr, buf: *const uint8_t, buf_size: size_t) -> c_int;
+    fn mclBnFr_isEqual(x: *const MclBnFr, y: *const MclBnFr) -> c_int;
+    fn mclBnFr_isZero(x: *const MclBnFr) -> c_int;
+    fn mclBnFr_isOne(x: *const MclBnFr) -> c_int;
+    fn mclBnFr_neg(y: *mut MclBnFr, x: *const MclBnFr);
+    fn mclBnFr_inv(y: *mut MclBnFr, x: *const MclBnFr);
+    fn mclBnFr_add(z: *mut MclBnFr, x: *const MclBnFr, y: *const MclBnFr);
+    fn mclBnFr_sub(z: *mut MclBnFr, x: *const MclBnFr, y: *const MclBnFr);
+    fn mclBnFr_mul(z: *mut MclBnFr, x: *const MclBnFr, y: *const MclBnFr);
+    fn mclBnFr_div(z: *mut MclBnFr, x: *const MclBnFr, y: *const MclBnFr);
+    fn mclBnFr_getLittleEndian(
+        buf: *mut uint8_t,
+        max_buf_size: size_t,
+        x: *const MclBnFr,
+    ) -> size_t;
+
+    fn mclBnG1_isValid(x: *const MclBnG1) -> c_int;
+    fn mclBnG1_isEqual(x: *const MclBnG1, y: *const MclBnG1) -> c_int;
+    fn mclBnG1_isZero(x: *const MclBnG1) -> c_int;
+    fn mclBnG1_neg(y: *mut MclBnG1, x: *const MclBnG1);
+    fn mclBnG1_add(z: *mut MclBnG1, x: *const MclBnG1, y: *const MclBnG1);
+    fn mclBnG1_sub(z: *mut MclBnG1, x: *const MclBnG1, y: *const MclBnG1);
+    fn mclBnG1_mul(z: *mut MclBnG1, x: *const MclBnG1, y: *const MclBnFr);
+    fn mclBnG1_normalize(y: *mut MclBnG1, x: *const MclBnG1);
+    #[cfg(not(feature = "pippenger"))]
+    fn mclBnG1_mulVec(z: *mut MclBnG1, x: *mut MclBnG1, y: *const MclBnFr, n: size_t);
+
+    fn mclBnG2_isValid(x: *const MclBnG2) -> c_int;
+    fn mclBnG2_isEqual(x: *const MclBnG2, y: *const MclBnG2) -> c_int;
+    fn mclBnG2_isZero(x: *const MclBnG2) -> c_int;
+    fn mclBnG2_neg(y: *mut MclBnG2, x: *const MclBnG2);
+    fn mclBnG2_add(z: *mut MclBnG2, x: *const MclBnG2, y: *const MclBnG2);
+    fn mclBnG2_sub(z: *mut MclBnG2, x: *const MclBnG2, y: *const MclBnG2);
+    fn mclBnG2_mul(z: *mut MclBnG2, x: *const MclBnG2, y: *const MclBnFr);
+    fn mclBnG2_normalize(y: *mut MclBnG2, x: *const MclBnG2);
+    #[cfg(not(feature = "pippenger"))]
+    fn mclBnG2_mulVec(z: *mut MclBnG2, x: *mut MclBnG2, y: *const MclBnFr, n: size_t);
+
+    fn mclBnGT_setInt(y: *mut MclBnGT, x: i64);
+    fn mclBnGT_isEqual(x: *const MclBnGT, y: *const MclBnGT) -> c_int;
+    fn mclBnGT_isOne(x: *const MclBnGT) -> c_int;
+    fn mclBnGT_inv(y: *mut MclBnGT, x: *const MclBnGT);
+    fn mclBnGT_mul(z: *mut MclBnGT, x: *const MclBnGT, y: *const MclBnGT);
+    fn mclBnGT_div(z: *mut MclBnGT, x: *const MclBnGT, y: *const MclBnGT);
+    fn mclBnGT_pow(z: *mut MclBnGT, x: *const MclBnGT, y: *const MclBnFr);
+
+    fn mclBn_pairing(z: *mut MclBnGT, x: *const MclBnG1, y: *const MclBnG2);
+    fn mclBn_finalExp(y: *mut MclBnGT, x: *const MclBnGT);
+    fn mclBn_millerLoop(z: *mut MclBnGT, x: *const MclBnG1, y: *const MclBnG2);
+    fn mclBn_millerLoopVec(z: *mut MclBnGT, x: *const MclBnG1, y: *const MclBnG2, n: size_t);
+
+    fn mclBn_setMapToMode(mode: c_int) -> c_int;
+    #[cfg(feature = "std")]
+    fn mclBn_setRandFunc(
+        this: *mut c_void,
+        read_func: Option<unsafe extern "C" fn(*mut c_void, *mut c_void, c_uint) -> c_uint>,
+    );
+    fn mclBn_getETHserialization() -> c_int;
+    fn mclBn_getFpByteSize() -> c_int;
+    fn mclBnFp_setStr(
+        x: *mut MclBnFp,
+        buf: *const c_char,
+        buf_size: size_t,
+        io_mode: c_int,
+    ) -> c_int;
+    fn mclBnFp_getStr(
+        buf: *mut c_char,
+        max_buf_size: size_t,
+        x: *const MclBnFp,
+        io_mode: c_int,
+    ) -> size_t;
+    fn mclBnFp_getLittleEndian(
+        buf: *mut uint8_t,
+        max_buf_size: size_t,
+        x: *const MclBnFp,
+    ) -> size_t;
+    fn mclBnFp_mapToG1(y: *mut MclBnG1, x: *const MclBnFp) -> c_int;
+    fn mclBnFp2_mapToG2(y: *mut MclBnG2, x: *const MclBnFp2) -> c_int;
+    fn mclBnG1_setStr(
+        x: *mut MclBnG1,
+        buf: *const c_char,
+        buf_size: size_t,
+        io_mode: c_int,
+    ) -> c_int;
+    fn mclBnG1_getStr(
+        buf: *mut c_char,
+        max_buf_size: size_t,
+        x: *const MclBnG1,
+        io_mode: c_int,
+    ) -> size_t;
+    fn mclBnG1_hashAndMapTo(x: *mut MclBnG1, buf: *const uint8_t, buf_size: size_t) -> c_int;
+    fn mclBnG1_setDst(dst: *const c_char, dst_size: size_t) -> c_int;
+    fn mclBnG1_hashAndMapToWithDst(
+        x: *mut MclBnG1,
+        buf: *const uint8_t,
+        buf_size: size_t,
+        dst: *const c_char,
+        dst_size: size_t,
+    ) -> c_int;
+    fn mclBnG2_setStr(
+        x: *mut MclBnG2,
+        buf: *const c_char,
+        buf_size: size_t,
+        io_mode: c_int,
+    ) -> c_int;
+    fn mclBnG2_getStr(
+        buf: *mut c_char,
+        max_buf_size: size_t,
+        x: *const MclBnG2,
+        io_mode: c_int,
+    ) -> size_t;
+    fn mclBnG2_hashAndMapTo(x: *mut MclBnG2, buf: *const uint8_t, buf_size: size_t) -> c_int;
+    fn mclBnG2_setDst(dst: *const c_char, dst_size: size_t) -> c_int;
+    fn mclBnG2_hashAndMapToWithDst(
+        x: *mut MclBnG2,
+        buf: *const uint8_t,
+        buf_size: size_t,
+        dst: *const c_char,
+        dst_size: size_t,
     ) -> c_int;
 }
 
 const COMPILED_VAR: c_int = MCLBN_FR_UNIT_SIZE * 10 + MCLBN_FP_UNIT_SIZE;
 
+// Random bytes reduced modulo the group order for a negligibly biased scalar
+const RANDOM_SCALAR_BYTES: usize = 64;
+
+// Upper bound for the serialized size of any supported type
+const MAX_SERIALIZED_SIZE: usize = 128;
+
+// Upper bound for exported strings, well above the binary form of a G2 point
+const MAX_STR_SIZE: usize = 1 << 14;
+
+// Size in bytes of the widest Fr supported by the linked library
+const FR_SIZE: usize = MCLBN_FR_UNIT_SIZE as usize * 8;
+
+// Size in bytes of the widest Fp, the width of each coordinate in `canonical_bytes`
+const FP_SIZE: usize = MCLBN_FP_UNIT_SIZE as usize * 8;
+
+// mcl io mode for the raw affine `x || y` coordinates of a point, only known
+// to recent mcl releases, which build.rs checks for
+const IO_EC_AFFINE_SERIALIZE: c_int = 4096;
+
+// Flag bits in the first byte of a ZCash encoded point
+const ZCASH_COMPRESSED_FLAG: u8 = 0x80;
+const ZCASH_INFINITY_FLAG: u8 = 0x40;
+const ZCASH_SIGN_FLAG: u8 = 0x20;
+
 #[derive(Debug, Copy, Clone)]
 #[repr(C)]
 pub enum CurveType {
@@ -169,6 +371,41 @@ pub enum CurveType {
     Bls12CurveFp381 = 5,
 }
 
+/// Method used to map hashes and field elements to curve points.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub enum MapToMode {
+    Original = 0,
+    TryAndIncrement = 1,
+    /// IETF hash-to-curve (SSWU) for BLS12-381.
+    HashToCurve = 5,
+}
+
+/// Byte encoding used when serializing and deserializing points (and scalars).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PointFormat {
+    /// herumi's native little-endian encoding.
+    Herumi,
+    /// The big-endian, flag-bit encoding of the ZCash BLS12-381 spec, shared with most other
+    /// BLS12-381 implementations.
+    ZCash,
+}
+
+/// String representation used by `set_str` and `get_str`.
+///
+/// Points are written as affine coordinates, `0` for the identity or `1 x y` otherwise.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub enum IoMode {
+    Bin = 2,
+    Dec = 10,
+    Hex = 16,
+    /// Binary with a `0b` prefix.
+    BinPrefix = 2 | 128,
+    /// Hex with a `0x` prefix.
+    HexPrefix = 16 | 128,
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct MclBnFr([uint64_t; MCLBN_FR_UNIT_SIZE as usize]);
@@ -177,6 +414,56 @@ impl MclBnFr {
     pub fn new() -> Self {
         Self([0; MCLBN_FR_UNIT_SIZE as usize])
     }
+
+    pub fn new_random() -> Result<Self, ()> {
+        let mut x = MclBnFr::new();
+        let res = unsafe { mclBnFr_setByCSPRNG(&mut x) };
+
+        if res == 0 {
+            Ok(x)
+        } else {
+            Err(())
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        unsafe { mclBnFr_isZero(self) == 1 }
+    }
+
+    pub fn is_one(&self) -> bool {
+        unsafe { mclBnFr_isOne(self) == 1 }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut y = MclBnFr::new();
+        unsafe {
+            mclBnFr_inv(&mut y, self);
+        }
+
+        y
+    }
+
+    /// Big-endian value, independent of the point format.
+    fn canonical_bytes(&self) -> [u8; FR_SIZE] {
+        let mut buf = [0u8; FR_SIZE];
+        unsafe {
+            mclBnFr_getLittleEndian(buf.as_mut_ptr(), buf.len(), self);
+        }
+        buf.reverse();
+
+        buf
+    }
+}
+
+impl From<i64> for MclBnFr {
+    fn from(x: i64) -> Self {
+        let mut y = MclBnFr::new();
+        unsafe {
+            mclBnFr_setInt(&mut y, x);
+        }
+
+        y
+    }
 }
 
 impl Default for MclBnFr {
@@ -193,6 +480,41 @@ impl MclBnG1 {
     pub fn new() -> Self {
         Self([0; MCLBN_FP_UNIT_SIZE as usize * 3])
     }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe { mclBnG1_isValid(self) == 1 }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        unsafe { mclBnG1_isZero(self) == 1 }
+    }
+
+    /// Big-endian affine coordinates `x || y`, all zero for the identity. Unlike `serialize`,
+    /// this doesn't depend on the point format.
+    fn canonical_bytes(&self) -> [u8; 2 * FP_SIZE] {
+        let mut buf = [0u8; 2 * FP_SIZE];
+        if !self.is_zero() {
+            let mut p = MclBnG1::new();
+            unsafe {
+                mclBnG1_normalize(&mut p, self);
+            }
+            write_coordinates(&p.0, &[0, 1], &mut buf);
+        }
+
+        buf
+    }
+
+    /// Maps a field element to a point using the current `MapToMode`.
+    pub fn map_to_from_fp(x: &MclBnFp) -> Result<Self, ()> {
+        let mut p = MclBnG1::new();
+        let res = unsafe { mclBnFp_mapToG1(&mut p, x) };
+
+        if res == 0 {
+            Ok(p)
+        } else {
+            Err(())
+        }
+    }
 }
 
 impl Default for MclBnG1 {
@@ -201,7 +523,7 @@ impl Default for MclBnG1 {
     }
 }
 
-impl std::fmt::Debug for MclBnG1 {
+impl core::fmt::Debug for MclBnG1 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "G1 ({:?})", self.0.iter().collect::<Vec<_>>())
     }
@@ -215,9 +537,44 @@ impl MclBnG2 {
     pub fn new() -> Self {
         Self([0; MCLBN_FP_UNIT_SIZE as usize * 2 * 3])
     }
+
+    pub fn is_valid(&self) -> bool {
+        unsafe { mclBnG2_isValid(self) == 1 }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        unsafe { mclBnG2_isZero(self) == 1 }
+    }
+
+    /// Big-endian affine coordinates `x.c1 || x.c0 || y.c1 || y.c0`, all zero for the identity.
+    /// Unlike `serialize`, this doesn't depend on the point format.
+    fn canonical_bytes(&self) -> [u8; 4 * FP_SIZE] {
+        let mut buf = [0u8; 4 * FP_SIZE];
+        if !self.is_zero() {
+            let mut p = MclBnG2::new();
+            unsafe {
+                mclBnG2_normalize(&mut p, self);
+            }
+            write_coordinates(&p.0, &[1, 0, 3, 2], &mut buf);
+        }
+
+        buf
+    }
+
+    /// Maps a field element to a point using the current `MapToMode`.
+    pub fn map_to_from_fp2(x: &MclBnFp2) -> Result<Self, ()> {
+        let mut p = MclBnG2::new();
+        let res = unsafe { mclBnFp2_mapToG2(&mut p, x) };
+
+        if res == 0 {
+            Ok(p)
+        } else {
+            Err(())
+        }
+    }
 }
 
-impl std::fmt::Debug for MclBnG2 {
+impl core::fmt::Debug for MclBnG2 {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "G2 ({:?})", self.0.iter().collect::<Vec<_>>())
     }
@@ -233,7 +590,51 @@ impl Default for MclBnG2 {
 #[repr(C)]
 pub struct MclBnGT([uint64_t; MCLBN_FP_UNIT_SIZE as usize * 12]);
 
-impl std::fmt::Debug for MclBnGT {
+impl MclBnGT {
+    pub fn new() -> Self {
+        Self([0; MCLBN_FP_UNIT_SIZE as usize * 12])
+    }
+
+    /// Returns the identity element of GT.
+    pub fn one() -> Self {
+        let mut x = MclBnGT::new();
+        unsafe {
+            mclBnGT_setInt(&mut x, 1);
+        }
+
+        x
+    }
+
+    pub fn is_one(&self) -> bool {
+        unsafe { mclBnGT_isOne(self) == 1 }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut y = MclBnGT::new();
+        unsafe {
+            mclBnGT_inv(&mut y, self);
+        }
+
+        y
+    }
+
+    pub fn pow(&self, exp: &MclBnFr) -> Self {
+        let mut y = MclBnGT::new();
+        unsafe {
+            mclBnGT_pow(&mut y, self, exp);
+        }
+
+        y
+    }
+}
+
+impl Default for MclBnGT {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for MclBnGT {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
         write!(f, "GT ({:?})", self.0.iter().collect::<Vec<_>>())
     }
@@ -243,10 +644,53 @@ impl std::fmt::Debug for MclBnGT {
 #[repr(C)]
 pub struct MclBnFp([uint64_t; MCLBN_FP_UNIT_SIZE as usize]);
 
+impl MclBnFp {
+    pub fn new() -> Self {
+        Self([0; MCLBN_FP_UNIT_SIZE as usize])
+    }
+}
+
+/// Writes the Fp elements of `limbs` at `indices` to `buf` as padded big-endian integers.
+fn write_coordinates(limbs: &[uint64_t], indices: &[usize], buf: &mut [u8]) {
+    let unit = MCLBN_FP_UNIT_SIZE as usize;
+
+    for (dst, i) in buf.chunks_mut(FP_SIZE).zip(indices.iter()) {
+        let mut x = MclBnFp::new();
+        x.0.copy_from_slice(&limbs[i * unit..(i + 1) * unit]);
+        unsafe {
+            mclBnFp_getLittleEndian(dst.as_mut_ptr(), dst.len(), &x);
+        }
+        dst.reverse();
+    }
+}
+
+impl Default for MclBnFp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct MclBnFp2([MclBnFp; 2]);
 
+impl MclBnFp2 {
+    pub fn new() -> Self {
+        Self([MclBnFp::new(), MclBnFp::new()])
+    }
+
+    /// Builds `c0 + c1 * i` from its coefficients.
+    pub fn from_coeffs(c0: MclBnFp, c1: MclBnFp) -> Self {
+        Self([c0, c1])
+    }
+}
+
+impl Default for MclBnFp2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct BlsId(MclBnFr);
@@ -257,12 +701,77 @@ impl BlsId {
             blsIdSetInt(self, x);
         }
     }
+
+    /// Builds an ID from the little-endian bytes of a non-zero element of Fr.
+    ///
+    /// Values at or above the group order are rejected rather than reduced, so arbitrary 32-byte
+    /// node identifiers should go through `hash_from` instead.
+    pub fn from_bytes_le(buf: &[u8]) -> Result<Self, ()> {
+        let mut id = BlsId::new();
+        let res = unsafe { blsIdSetLittleEndian(&mut id, buf.as_ptr(), buf.len()) };
+
+        // herumi masks the input to the bit length of the order instead of failing, so check that
+        // the value survived unchanged
+        let mut value = [0u8; FR_SIZE];
+        unsafe {
+            mclBnFr_getLittleEndian(value.as_mut_ptr(), value.len(), &id.0);
+        }
+        let canonical = buf
+            .iter()
+            .enumerate()
+            .all(|(i, b)| value.get(i).copied().unwrap_or(0) == *b);
+
+        // The share at zero would be the master secret itself
+        let non_zero = value.iter().any(|b| *b != 0);
+
+        if res == 0 && canonical && non_zero {
+            Ok(id)
+        } else {
+            Err(())
+        }
+    }
+
+    /// Builds an ID by hashing arbitrary bytes and reducing the digest into Fr.
+    pub fn hash_from(buf: &[u8]) -> Result<Self, ()> {
+        let mut id = BlsId::new();
+        let res = unsafe { mclBnFr_setHashOf(&mut id.0, buf.as_ptr(), buf.len()) };
+
+        if res == 0 {
+            Ok(id)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Fails for zero, see `BlsId::from_bytes_le`.
+impl TryFrom<u64> for BlsId {
+    type Error = ();
+
+    fn try_from(x: u64) -> Result<Self, ()> {
+        BlsId::from_bytes_le(&x.to_le_bytes())
+    }
 }
 
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct BlsSecretKey(MclBnFr);
 
+/// Compares the reduced limbs of both keys without branching on their values.
+impl ConstantTimeEq for BlsSecretKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0 .0[..].ct_eq(&other.0 .0[..])
+    }
+}
+
+impl PartialEq for BlsSecretKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for BlsSecretKey {}
+
 impl BlsSecretKey {
     pub fn new_random() -> Result<Self, ()> {
         let mut sk = BlsSecretKey::new();
@@ -275,6 +784,36 @@ impl BlsSecretKey {
         }
     }
 
+    /// Generates a key from `rng` instead of herumi's CSPRNG.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let mut buf = [0u8; RANDOM_SCALAR_BYTES];
+        let mut sk = BlsSecretKey::new();
+
+        while sk.0.is_zero() {
+            rng.fill_bytes(&mut buf);
+            sk.set_little_endian_mod(&buf);
+        }
+
+        sk
+    }
+
+    /// Generates the master keys of a `threshold` sharing of `self`, to be passed to `new_share`.
+    ///
+    /// The first key is `self` and the rest are random coefficients drawn from `rng`.
+    pub fn new_master_keys<R: RngCore + CryptoRng>(
+        &self,
+        threshold: usize,
+        rng: &mut R,
+    ) -> Vec<BlsSecretKey> {
+        let mut msk = Vec::with_capacity(threshold);
+        msk.push(self.clone());
+        for _ in 1..threshold {
+            msk.push(BlsSecretKey::random(rng));
+        }
+
+        msk
+    }
+
     pub fn set_little_endian(&mut self, buf: &[u8]) {
         unsafe {
             blsSecretKeySetLittleEndian(self, buf.as_ptr(), buf.len());
@@ -287,6 +826,24 @@ impl BlsSecretKey {
         }
     }
 
+    /// Returns the key as a 32-byte big-endian integer, independent of the serialization mode.
+    pub(crate) fn to_be_bytes(&self) -> [u8; 32] {
+        let hex_str = self.get_hex_str().unwrap_or_default();
+
+        // Left-pad to the full 64 hex digits
+        let mut padded = [b'0'; 64];
+        padded[64 - hex_str.len()..].copy_from_slice(hex_str.as_bytes());
+
+        let mut buf = [0u8; 32];
+        hex::decode_to_slice(&padded[..], &mut buf).expect("Fr fits into 32 bytes");
+
+        buf
+    }
+
+    pub fn inverse(&self) -> Self {
+        BlsSecretKey(self.0.inverse())
+    }
+
     pub fn to_public_key(&self) -> BlsPublicKey {
         let mut pk = BlsPublicKey::new();
         unsafe {
@@ -306,19 +863,147 @@ impl BlsSecretKey {
     }
 }
 
+impl Div<&BlsSecretKey> for BlsSecretKey {
+    type Output = BlsSecretKey;
+    fn div(self, other: &BlsSecretKey) -> Self::Output {
+        BlsSecretKey(self.0 / other.0.clone())
+    }
+}
+
+impl Div<&MclBnFr> for BlsSecretKey {
+    type Output = BlsSecretKey;
+    fn div(self, other: &MclBnFr) -> Self::Output {
+        BlsSecretKey(self.0 / other.clone())
+    }
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct BlsPublicKey(MclBnG2);
 
+impl BlsPublicKey {
+    /// Returns the G2 generator that secret keys are multiplied by to get public keys.
+    pub fn generator() -> Self {
+        let mut q = BlsPublicKey::new();
+        unsafe {
+            blsGetGeneratorOfPublicKey(&mut q);
+        }
+
+        q
+    }
+
+    /// Replaces the generator used by `to_public_key` and signature verification.
+    ///
+    /// IETF BLS signatures over BLS12-381 need the standard G2 generator instead of herumi's.
+    pub fn set_generator(q: &BlsPublicKey) -> Result<(), ()> {
+        let res = unsafe { blsSetGeneratorOfPublicKey(q) };
+
+        if res == 0 {
+            Ok(())
+        } else {
+            Err(())
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[repr(C)]
 pub struct BlsSignature(MclBnG1);
 
 impl BlsSignature {
     pub fn verify(&self, public_key: &BlsPublicKey, msg: &[u8]) -> bool {
-        let res = unsafe { blsVerify(self, public_key, msg.as_ptr(), msg.len()) };
+        self.verify_aggregate(core::slice::from_ref(public_key), &[msg])
+    }
+
+    /// Verifies an aggregate of signatures of distinct messages, one per public key.
+    pub fn verify_aggregate(&self, public_keys: &[BlsPublicKey], msgs: &[&[u8]]) -> bool {
+        // The identity public key accepts the identity signature for any message
+        if public_keys.is_empty()
+            || public_keys.len() != msgs.len()
+            || public_keys.iter().any(BlsPublicKey::is_zero)
+        {
+            return false;
+        }
+
+        // Without a proof of possession for every key, aggregates are only unforgeable when the
+        // messages are distinct, otherwise a rogue key can cancel out the other signers
+        let mut distinct = BTreeSet::new();
+        if !msgs.iter().all(|msg| distinct.insert(*msg)) {
+            return false;
+        }
+
+        // e(sig, Q) == prod(e(H(m_i), pk_i))
+        let mut pairs = vec![(-self.0.clone(), BlsPublicKey::generator().0)];
+        for (pk, msg) in public_keys.iter().zip(msgs.iter()) {
+            match MclBnG1::hash_and_map_to(msg) {
+                Ok(h) => pairs.push((h, pk.0.clone())),
+                Err(_) => return false,
+            }
+        }
+
+        pairing_product_is_one(&pairs)
+    }
+
+    /// Verifies many independent signatures at once, randomizing each one so that invalid
+    /// signatures can't cancel each other out.
+    ///
+    /// The randomizers come from the operating system, never from the RNG installed by `set_rng`.
+    #[cfg(feature = "std")]
+    pub fn verify_batch(
+        signatures: &[BlsSignature],
+        public_keys: &[BlsPublicKey],
+        msgs: &[&[u8]],
+    ) -> bool {
+        Self::verify_batch_with_rng(signatures, public_keys, msgs, &mut OsRng)
+    }
+
+    /// Like `verify_batch`, drawing the randomizers from `rng`.
+    ///
+    /// Anyone who can predict `rng` can forge a batch that verifies, so it must be a CSPRNG that
+    /// is kept away from signers. Signatures outside of the prime-order subgroup are rejected
+    /// whether or not strict deserialization is enabled.
+    pub fn verify_batch_with_rng<R: RngCore + CryptoRng>(
+        signatures: &[BlsSignature],
+        public_keys: &[BlsPublicKey],
+        msgs: &[&[u8]],
+        rng: &mut R,
+    ) -> bool {
+        if signatures.is_empty()
+            || signatures.len() != public_keys.len()
+            || signatures.len() != msgs.len()
+            || public_keys.iter().any(BlsPublicKey::is_zero)
+            || !signatures.iter().all(BlsSignature::is_in_subgroup)
+        {
+            return false;
+        }
 
-        res == 1
+        // e(sum(r_i * sig_i), Q) == prod(e(r_i * H(m_i), pk_i))
+        let mut agg_sig = MclBnG1::new();
+        let mut pairs = Vec::with_capacity(signatures.len() + 1);
+        for ((sig, pk), msg) in signatures.iter().zip(public_keys.iter()).zip(msgs.iter()) {
+            let r = BlsSecretKey::random(rng).0;
+            let h = match MclBnG1::hash_and_map_to(msg) {
+                Ok(h) => h,
+                Err(_) => return false,
+            };
+
+            agg_sig += sig.0.clone() * r.clone();
+            pairs.push((h * r, pk.0.clone()));
+        }
+        pairs.push((-agg_sig, BlsPublicKey::generator().0));
+
+        pairing_product_is_one(&pairs)
+    }
+}
+
+/// Enables subgroup checks when deserializing or parsing public keys and signatures.
+///
+/// Strict mode rejects points outside of the prime-order subgroup at the cost of a scalar
+/// multiplication per point.
+pub fn set_strict_deserialization(strict: bool) {
+    unsafe {
+        blsPublicKeyVerifyOrder(strict as c_int);
+        blsSignatureVerifyOrder(strict as c_int);
     }
 }
 
@@ -328,27 +1013,162 @@ pub fn bls_init(curve: CurveType) -> Result<(), isize> {
     if res == 0 {
         Ok(())
     } else {
+        #[cfg(feature = "std")]
         eprintln!("Error: {}", res);
         Err(res as isize)
     }
 }
 
-macro_rules! impl_api {
-    ($data_type:ty, $api_name:ident) => {
-        paste::item! {
-            impl [<Bls $api_name>] {
-                pub fn new() -> Self {
-                    Self($data_type::new())
-                }
+/// Selects how hashes are mapped to curve points, including those signed by `BlsSecretKey::sign`.
+pub fn set_map_to_mode(mode: MapToMode) -> Result<(), ()> {
+    let res = unsafe { mclBn_setMapToMode(mode as c_int) };
 
-                pub fn serialize(&self, buf: &mut [u8]) -> Result<usize, ()> {
-                    let size = unsafe { [<bls $api_name Serialize>] (buf.as_mut_ptr(), buf.len(), self) };
+    if res == 0 {
+        Ok(())
+    } else {
+        Err(())
+    }
+}
 
-                    if size == 0 {
-                        Err(())
-                    } else {
-                        Ok(size)
-                    }
+/// Selects the byte encoding used by `serialize`/`deserialize` and the point encoding methods.
+pub fn set_point_format(format: PointFormat) {
+    unsafe {
+        blsSetETHserialization((format == PointFormat::ZCash) as c_int);
+    }
+}
+
+pub fn point_format() -> PointFormat {
+    if unsafe { mclBn_getETHserialization() } == 1 {
+        PointFormat::ZCash
+    } else {
+        PointFormat::Herumi
+    }
+}
+
+#[cfg(feature = "std")]
+static RNG: Mutex<Option<Box<dyn RngCore + Send>>> = Mutex::new(None);
+
+#[cfg(feature = "std")]
+unsafe extern "C" fn read_rng(_this: *mut c_void, buf: *mut c_void, buf_size: c_uint) -> c_uint {
+    let buf = core::slice::from_raw_parts_mut(buf as *mut u8, buf_size as usize);
+
+    let mut rng = match RNG.lock() {
+        Ok(rng) => rng,
+        Err(_) => return 0,
+    };
+
+    match rng.as_mut().map(|rng| rng.try_fill_bytes(buf)) {
+        Some(Ok(())) => buf_size,
+        _ => 0,
+    }
+}
+
+/// Installs `rng` as the source of herumi's CSPRNG.
+///
+/// Every key, scalar and sharing polynomial generated by the library, including those from
+/// `new_random`, is then drawn from `rng`. Verification randomness is excluded: the randomizers
+/// of `BlsSignature::verify_batch` always come from the operating system.
+#[cfg(feature = "std")]
+pub fn set_rng<R: RngCore + CryptoRng + Send + 'static>(rng: R) {
+    *RNG.lock().unwrap_or_else(|err| err.into_inner()) = Some(Box::new(rng));
+    unsafe {
+        mclBn_setRandFunc(core::ptr::null_mut(), Some(read_rng));
+    }
+}
+
+/// Restores herumi's own CSPRNG.
+#[cfg(feature = "std")]
+pub fn reset_rng() {
+    unsafe {
+        mclBn_setRandFunc(core::ptr::null_mut(), None);
+    }
+    *RNG.lock().unwrap_or_else(|err| err.into_inner()) = None;
+}
+
+/// Computes the reduced pairing `e(p, q)`.
+pub fn pairing(p: &MclBnG1, q: &MclBnG2) -> MclBnGT {
+    let mut e = MclBnGT::new();
+    unsafe {
+        mclBn_pairing(&mut e, p, q);
+    }
+
+    e
+}
+
+/// Computes the Miller loop of `e(p, q)` without the final exponentiation.
+pub fn miller_loop(p: &MclBnG1, q: &MclBnG2) -> MclBnGT {
+    let mut e = MclBnGT::new();
+    unsafe {
+        mclBn_millerLoop(&mut e, p, q);
+    }
+
+    e
+}
+
+/// Checks that `prod(e(p_i, q_i)) == 1` with a shared Miller loop and one final exponentiation.
+pub fn pairing_product_is_one(pairs: &[(MclBnG1, MclBnG2)]) -> bool {
+    if pairs.is_empty() {
+        return true;
+    }
+
+    let (g1, g2): (Vec<MclBnG1>, Vec<MclBnG2>) = pairs.iter().cloned().unzip();
+
+    let mut e = MclBnGT::new();
+    unsafe {
+        mclBn_millerLoopVec(&mut e, g1.as_ptr(), g2.as_ptr(), pairs.len());
+    }
+
+    final_exp(&e).is_one()
+}
+
+pub fn final_exp(x: &MclBnGT) -> MclBnGT {
+    let mut e = MclBnGT::new();
+    unsafe {
+        mclBn_finalExp(&mut e, x);
+    }
+
+    e
+}
+
+/// Calls a herumi `getStr`-style function with a growing buffer until the string fits.
+fn get_str_growing<F>(get_str: F) -> Result<String, ()>
+where
+    F: Fn(&mut [u8]) -> size_t,
+{
+    let mut size = 128;
+
+    while size <= MAX_STR_SIZE {
+        let mut buf = vec![0u8; size];
+        let len = get_str(&mut buf);
+
+        // Zero means the string (plus its null terminator) didn't fit
+        if len > 0 && len < size {
+            buf.truncate(len);
+            return String::from_utf8(buf).map_err(|_| ());
+        }
+
+        size *= 2;
+    }
+
+    Err(())
+}
+
+macro_rules! impl_api {
+    ($data_type:ty, $api_name:ident) => {
+        paste::item! {
+            impl [<Bls $api_name>] {
+                pub fn new() -> Self {
+                    Self($data_type::new())
+                }
+
+                pub fn serialize(&self, buf: &mut [u8]) -> Result<usize, ()> {
+                    let size = unsafe { [<bls $api_name Serialize>] (buf.as_mut_ptr(), buf.len(), self) };
+
+                    if size == 0 {
+                        Err(())
+                    } else {
+                        Ok(size)
+                    }
                 }
 
                 pub fn deserialize(&mut self, buf: &[u8]) -> Result<usize, ()> {
@@ -362,82 +1182,59 @@ macro_rules! impl_api {
                 }
 
                 pub fn set_dec_str(&mut self, dec_str: &str) -> Result<(), ()> {
-                    let buf = dec_str.as_bytes();
-                    let c_str = CString::new(dec_str);
-
-                    match c_str {
-                        Ok(s) => {
-                            let res = unsafe { [<bls $api_name SetDecStr>] (self, s.as_ptr(), buf.len()) };
+                    let res = unsafe {
+                        [<bls $api_name SetDecStr>] (self, dec_str.as_ptr() as *const c_char, dec_str.len())
+                    };
 
-                            if res == 0 {
-                                Ok(())
-                            } else {
-                                Err(())
-                            }
-                        }
-                        Err(_) => Err(())?,
+                    if res == 0 {
+                        Ok(())
+                    } else {
+                        Err(())
                     }
                 }
 
                 pub fn get_dec_str(&self) -> Result<String, ()> {
-                    let mut buf = [0u8; 128];
-                    let res = unsafe { [<bls $api_name GetDecStr>] (buf.as_mut_ptr(), buf.len(), self) };
+                    get_str_growing(|buf| unsafe {
+                        [<bls $api_name GetDecStr>] (buf.as_mut_ptr(), buf.len(), self)
+                    })
+                }
+
+                pub fn set_hex_str(&mut self, hex_str: &str) -> Result<(), ()> {
+                    let res = unsafe {
+                        [<bls $api_name SetHexStr>] (self, hex_str.as_ptr() as *const c_char, hex_str.len())
+                    };
+
                     if res == 0 {
-                        Err(())
+                        Ok(())
                     } else {
-                        let mut buf = buf.iter().map(|c| *c)
-                            .take_while(|n| *n != 0)
-                            .collect::<Vec<u8>>();
-                        buf.push(0u8); // Append null-terminator
-
-                        let s = CStr::from_bytes_with_nul(&buf);
-                        if let Ok(s) = s {
-                            Ok(s.to_string_lossy().to_string())
-                        } else {
-                            Err(())
-                        }
+                        Err(())
                     }
                 }
 
-                pub fn set_hex_str(&mut self, dec_str: &str) -> Result<(), ()> {
-                    let buf = dec_str.as_bytes();
-                    let c_str = CString::new(dec_str);
-
-                    match c_str {
-                        Ok(s) => {
-                            let res = unsafe { [<bls $api_name SetHexStr>] (self, s.as_ptr(), buf.len()) };
+                pub fn get_hex_str(&self) -> Result<String, ()> {
+                    get_str_growing(|buf| unsafe {
+                        [<bls $api_name GetHexStr>] (buf.as_mut_ptr(), buf.len(), self)
+                    })
+                }
 
-                            if res == 0 {
-                                Ok(())
-                            } else {
-                                Err(())
-                            }
-                        }
-                        Err(_) => Err(())?,
-                    }
+                /// Exports the underlying field element or point as a string in the given mode.
+                pub fn get_str(&self, mode: IoMode) -> Result<String, ()> {
+                    self.0.get_str(mode)
                 }
+            }
 
-                pub fn get_hex_str(&self) -> Result<String, ()> {
-                    let mut buf = [0u8; 128];
-                    let res = unsafe { [<bls $api_name GetHexStr>] (buf.as_mut_ptr(), buf.len(), self) };
-                    if res == 0 {
-                        Err(())
-                    } else {
-                        let mut buf = buf.iter().map(|c| *c)
-                            .take_while(|n| *n != 0)
-                            .collect::<Vec<u8>>();
-                        buf.push(0u8);
-
-                        let s = CStr::from_bytes_with_nul(&buf);
-                        if let Ok(s) = s {
-                            Ok(s.to_string_lossy().to_string())
-                        } else {
-                            Err(())
-                        }
-                    }
+            impl Default for [<Bls $api_name>] {
+                fn default() -> Self {
+                    Self::new()
                 }
             }
+        }
+    }
+}
 
+macro_rules! impl_eq {
+    ($api_name:ident) => {
+        paste::item! {
             impl PartialEq for [<Bls $api_name>] {
                 fn eq(&self, other: &Self) -> bool {
                     let res = unsafe { [<bls $api_name IsEqual>](self, other) };
@@ -446,14 +1243,8 @@ macro_rules! impl_api {
             }
 
             impl Eq for [<Bls $api_name>] {}
-
-            impl Default for [<Bls $api_name>] {
-                fn default() -> Self {
-                    Self::new()
-                }
-            }
         }
-    }
+    };
 }
 
 macro_rules! impl_ops {
@@ -496,6 +1287,328 @@ macro_rules! impl_ops {
                     }
                 }
             }
+
+            impl<'a, 'b> Add<&'b [<Bls $api_name>]> for &'a [<Bls $api_name>] {
+                type Output = [<Bls $api_name>];
+                fn add(self, other: &'b [<Bls $api_name>]) -> Self::Output {
+                    let mut result = self.clone();
+                    result += other;
+
+                    result
+                }
+            }
+
+            impl<'a> AddAssign<&'a [<Bls $api_name>]> for [<Bls $api_name>] {
+                fn add_assign(&mut self, other: &'a [<Bls $api_name>]) {
+                    unsafe {
+                        [<bls $api_name Add>](self, other);
+                    }
+                }
+            }
+
+            impl<'a, 'b> Sub<&'b [<Bls $api_name>]> for &'a [<Bls $api_name>] {
+                type Output = [<Bls $api_name>];
+                fn sub(self, other: &'b [<Bls $api_name>]) -> Self::Output {
+                    let mut result = self.clone();
+                    result -= other;
+
+                    result
+                }
+            }
+
+            impl<'a> SubAssign<&'a [<Bls $api_name>]> for [<Bls $api_name>] {
+                fn sub_assign(&mut self, other: &'a [<Bls $api_name>]) {
+                    unsafe {
+                        [<bls $api_name Sub>](self, other);
+                    }
+                }
+            }
+
+            impl Sum for [<Bls $api_name>] {
+                fn sum<I: Iterator<Item = [<Bls $api_name>]>>(iter: I) -> Self {
+                    iter.fold([<Bls $api_name>]::new(), |mut acc, x| {
+                        acc += &x;
+                        acc
+                    })
+                }
+            }
+
+            impl<'a> Sum<&'a [<Bls $api_name>]> for [<Bls $api_name>] {
+                fn sum<I: Iterator<Item = &'a [<Bls $api_name>]>>(iter: I) -> Self {
+                    iter.fold([<Bls $api_name>]::new(), |mut acc, x| {
+                        acc += x;
+                        acc
+                    })
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_scalar_ops {
+    ($api_name:ident) => {
+        paste::item! {
+            impl Mul<&BlsSecretKey> for [<Bls $api_name>] {
+                type Output = [<Bls $api_name>];
+                fn mul(mut self, scalar: &BlsSecretKey) -> Self::Output {
+                    self *= scalar;
+                    self
+                }
+            }
+
+            impl Mul<&MclBnFr> for [<Bls $api_name>] {
+                type Output = [<Bls $api_name>];
+                fn mul(mut self, scalar: &MclBnFr) -> Self::Output {
+                    self *= scalar;
+                    self
+                }
+            }
+
+            impl MulAssign<&BlsSecretKey> for [<Bls $api_name>] {
+                fn mul_assign(&mut self, scalar: &BlsSecretKey) {
+                    unsafe {
+                        [<bls $api_name Mul>](self, scalar);
+                    }
+                }
+            }
+
+            impl MulAssign<&MclBnFr> for [<Bls $api_name>] {
+                fn mul_assign(&mut self, scalar: &MclBnFr) {
+                    // BlsSecretKey is a transparent wrapper around MclBnFr
+                    let scalar = scalar as *const MclBnFr as *const BlsSecretKey;
+                    unsafe {
+                        [<bls $api_name Mul>](self, scalar);
+                    }
+                }
+            }
+
+            impl Neg for [<Bls $api_name>] {
+                type Output = [<Bls $api_name>];
+                fn neg(mut self) -> Self::Output {
+                    unsafe {
+                        [<bls $api_name Neg>](&mut self);
+                    }
+
+                    self
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_point_checks {
+    ($api_name:ident) => {
+        paste::item! {
+            impl [<Bls $api_name>] {
+                /// Checks that the point is on the curve.
+                pub fn is_valid(&self) -> bool {
+                    self.0.is_valid()
+                }
+
+                /// Checks whether the point is the identity.
+                pub fn is_zero(&self) -> bool {
+                    unsafe { [<bls $api_name IsZero>](self) == 1 }
+                }
+
+                /// Checks that the point lies in the prime-order subgroup.
+                pub fn is_in_subgroup(&self) -> bool {
+                    unsafe { [<bls $api_name IsValidOrder>](self) == 1 }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_point_encoding {
+    ($api_name:ident, $name:ident, $coords:expr) => {
+        paste::item! {
+            impl [<Bls $api_name>] {
+                /// Size in bytes of the compressed encoding.
+                pub fn compressed_size() -> usize {
+                    unsafe { mclBn_getFpByteSize() as usize * $coords }
+                }
+
+                /// Size in bytes of the uncompressed encoding.
+                pub fn uncompressed_size() -> usize {
+                    Self::compressed_size() * 2
+                }
+
+                /// Encodes the point as its x coordinate and the sign of y.
+                pub fn to_compressed(&self) -> Vec<u8> {
+                    let mut buf = vec![0u8; Self::compressed_size()];
+                    self.serialize(&mut buf)
+                        .expect("buffer fits a compressed point");
+
+                    buf
+                }
+
+                /// Encodes the point as both of its affine coordinates.
+                pub fn to_uncompressed(&self) -> Result<Vec<u8>, ()> {
+                    // mcl NUL terminates its output
+                    let mut buf = vec![0u8; Self::uncompressed_size() + 1];
+
+                    if self.is_zero() {
+                        if point_format() == PointFormat::ZCash {
+                            buf[0] = ZCASH_INFINITY_FLAG;
+                        }
+                        buf.truncate(Self::uncompressed_size());
+                        return Ok(buf);
+                    }
+
+                    let size = unsafe {
+                        [<mclBn $name _getStr>](
+                            buf.as_mut_ptr() as *mut c_char,
+                            buf.len(),
+                            &self.0,
+                            IO_EC_AFFINE_SERIALIZE,
+                        )
+                    };
+                    if size != Self::uncompressed_size() {
+                        return Err(());
+                    }
+                    buf.truncate(size);
+
+                    Ok(buf)
+                }
+
+                pub fn from_compressed(buf: &[u8]) -> Result<Self, ()> {
+                    if buf.len() != Self::compressed_size() {
+                        return Err(());
+                    }
+
+                    let mut p = Self::new();
+                    match p.deserialize(buf) {
+                        Ok(size) if size == buf.len() => Ok(p),
+                        _ => Err(()),
+                    }
+                }
+
+                pub fn from_uncompressed(buf: &[u8]) -> Result<Self, ()> {
+                    if buf.len() != Self::uncompressed_size() {
+                        return Err(());
+                    }
+
+                    if point_format() == PointFormat::ZCash {
+                        let flags = buf[0] & (ZCASH_COMPRESSED_FLAG | ZCASH_INFINITY_FLAG | ZCASH_SIGN_FLAG);
+
+                        if flags == ZCASH_INFINITY_FLAG {
+                            return if buf[0] == flags && buf[1..].iter().all(|b| *b == 0) {
+                                Ok(Self::new())
+                            } else {
+                                Err(())
+                            };
+                        } else if flags != 0 {
+                            return Err(());
+                        }
+                    } else if buf.iter().all(|b| *b == 0) {
+                        return Ok(Self::new());
+                    }
+
+                    let mut p = Self::new();
+                    let res = unsafe {
+                        [<mclBn $name _setStr>](
+                            &mut p.0,
+                            buf.as_ptr() as *const c_char,
+                            buf.len(),
+                            IO_EC_AFFINE_SERIALIZE,
+                        )
+                    };
+
+                    if res == 0 {
+                        Ok(p)
+                    } else {
+                        Err(())
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_parse {
+    ($api_name:ident) => {
+        paste::item! {
+            impl TryFrom<&[u8]> for [<Bls $api_name>] {
+                type Error = ();
+
+                /// Deserializes from exactly the serialized bytes.
+                fn try_from(buf: &[u8]) -> Result<Self, ()> {
+                    let mut x = Self::new();
+
+                    match x.deserialize(buf) {
+                        Ok(size) if size == buf.len() => Ok(x),
+                        _ => Err(()),
+                    }
+                }
+            }
+
+            impl FromStr for [<Bls $api_name>] {
+                type Err = ();
+
+                /// Parses the hex of the serialized bytes, with or without a `0x` prefix.
+                fn from_str(s: &str) -> Result<Self, ()> {
+                    let hex_str = s.strip_prefix("0x").unwrap_or(s);
+                    let buf = hex::decode(hex_str).map_err(|_| ())?;
+
+                    Self::try_from(buf.as_slice())
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_display {
+    ($api_name:ident) => {
+        paste::item! {
+            /// Hex of the serialized bytes, `0x` prefixed with `{:#x}`.
+            impl core::fmt::LowerHex for [<Bls $api_name>] {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    let mut buf = [0u8; MAX_SERIALIZED_SIZE];
+                    let size = self.serialize(&mut buf).map_err(|_| Error)?;
+
+                    if f.alternate() {
+                        write!(f, "0x")?;
+                    }
+                    for b in &buf[..size] {
+                        write!(f, "{:02x}", b)?;
+                    }
+
+                    Ok(())
+                }
+            }
+
+            /// `0x` prefixed hex of the serialized bytes, as accepted by `FromStr`.
+            impl core::fmt::Display for [<Bls $api_name>] {
+                fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+                    write!(f, "{:#x}", self)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_hash_ord {
+    ($api_name:ident) => {
+        paste::item! {
+            impl Hash for [<Bls $api_name>] {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.canonical_bytes().hash(state);
+                }
+            }
+
+            /// Orders by the big-endian scalar or affine coordinates, consistent with `Eq` and
+            /// independent of the point format.
+            impl Ord for [<Bls $api_name>] {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.0.canonical_bytes().cmp(&other.0.canonical_bytes())
+                }
+            }
+
+            impl PartialOrd for [<Bls $api_name>] {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
         }
     };
 }
@@ -540,17 +1653,257 @@ macro_rules! impl_recover {
     }
 }
 
+macro_rules! impl_mcl_eq {
+    ($name:ident) => {
+        paste::item! {
+            impl PartialEq for [<MclBn $name>] {
+                fn eq(&self, other: &Self) -> bool {
+                    unsafe { [<mclBn $name _isEqual>](self, other) == 1 }
+                }
+            }
+
+            impl Eq for [<MclBn $name>] {}
+        }
+    };
+}
+
+macro_rules! impl_mcl_op {
+    ($name:ident, $op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, $mcl_fn:ident, $rhs:ty) => {
+        paste::item! {
+            impl $op<$rhs> for [<MclBn $name>] {
+                type Output = [<MclBn $name>];
+                fn $op_fn(self, other: $rhs) -> Self::Output {
+                    let mut z = [<MclBn $name>]::new();
+                    unsafe {
+                        [<mclBn $name _ $mcl_fn>](&mut z, &self, &other);
+                    }
+
+                    z
+                }
+            }
+
+            impl $op_assign<$rhs> for [<MclBn $name>] {
+                fn $op_assign_fn(&mut self, other: $rhs) {
+                    let x = self.clone();
+                    unsafe {
+                        [<mclBn $name _ $mcl_fn>](self, &x, &other);
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_mcl_neg {
+    ($name:ident) => {
+        paste::item! {
+            impl Neg for [<MclBn $name>] {
+                type Output = [<MclBn $name>];
+                fn neg(self) -> Self::Output {
+                    let mut y = [<MclBn $name>]::new();
+                    unsafe {
+                        [<mclBn $name _neg>](&mut y, &self);
+                    }
+
+                    y
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_mcl_group_ops {
+    ($name:ident) => {
+        impl_mcl_eq!($name);
+        impl_mcl_neg!($name);
+        paste::item! {
+            impl_mcl_op!($name, Add, add, AddAssign, add_assign, add, [<MclBn $name>]);
+            impl_mcl_op!($name, Sub, sub, SubAssign, sub_assign, sub, [<MclBn $name>]);
+        }
+    };
+}
+
+macro_rules! impl_conversions {
+    ($data_type:ident, $api_name:ident) => {
+        paste::item! {
+            impl From<[<Bls $api_name>]> for $data_type {
+                fn from(x: [<Bls $api_name>]) -> Self {
+                    x.0
+                }
+            }
+
+            impl From<$data_type> for [<Bls $api_name>] {
+                fn from(x: $data_type) -> Self {
+                    Self(x)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_mcl_str {
+    ($name:ident) => {
+        paste::item! {
+            impl [<MclBn $name>] {
+                pub fn set_str(&mut self, s: &str, mode: IoMode) -> Result<(), ()> {
+                    let res = unsafe {
+                        [<mclBn $name _setStr>](self, s.as_ptr() as *const c_char, s.len(), mode as c_int)
+                    };
+
+                    if res == 0 {
+                        Ok(())
+                    } else {
+                        Err(())
+                    }
+                }
+
+                pub fn get_str(&self, mode: IoMode) -> Result<String, ()> {
+                    get_str_growing(|buf| unsafe {
+                        [<mclBn $name _getStr>](
+                            buf.as_mut_ptr() as *mut c_char,
+                            buf.len(),
+                            self,
+                            mode as c_int,
+                        )
+                    })
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_hash_to_curve {
+    ($name:ident) => {
+        paste::item! {
+            impl [<MclBn $name>] {
+                /// Hashes `msg` to a point using the current `MapToMode`.
+                pub fn hash_and_map_to(msg: &[u8]) -> Result<Self, ()> {
+                    let mut p = [<MclBn $name>]::new();
+                    let res = unsafe { [<mclBn $name _hashAndMapTo>](&mut p, msg.as_ptr(), msg.len()) };
+
+                    if res == 0 {
+                        Ok(p)
+                    } else {
+                        Err(())
+                    }
+                }
+
+                /// Sets the domain separation tag `hash_and_map_to`, and therefore signing, uses
+                /// under `MapToMode::HashToCurve`.
+                pub fn set_dst(dst: &[u8]) -> Result<(), ()> {
+                    let res = unsafe { [<mclBn $name _setDst>](dst.as_ptr() as *const c_char, dst.len()) };
+
+                    if res == 0 {
+                        Ok(())
+                    } else {
+                        Err(())
+                    }
+                }
+
+                /// Hashes `msg` to a point with the IETF hash-to-curve domain separation tag `dst`.
+                ///
+                /// Requires `MapToMode::HashToCurve` on `CurveType::Bls12CurveFp381`.
+                pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Result<Self, ()> {
+                    let mut p = [<MclBn $name>]::new();
+                    let res = unsafe {
+                        [<mclBn $name _hashAndMapToWithDst>](
+                            &mut p,
+                            msg.as_ptr(),
+                            msg.len(),
+                            dst.as_ptr() as *const c_char,
+                            dst.len(),
+                        )
+                    };
+
+                    if res == 0 {
+                        Ok(p)
+                    } else {
+                        Err(())
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_multi_exp {
+    ($name:ident) => {
+        paste::item! {
+            impl [<MclBn $name>] {
+                /// Computes `sum(points[i] * scalars[i])`, which is much faster than summing the
+                /// products one by one.
+                pub fn multi_exp(points: &[[<MclBn $name>]], scalars: &[MclBnFr]) -> Result<Self, ()> {
+                    if points.len() != scalars.len() {
+                        return Err(());
+                    }
+
+                    #[cfg(not(feature = "pippenger"))]
+                    {
+                        // mcl may normalize the points in place
+                        let mut points = points.to_vec();
+                        let mut z = [<MclBn $name>]::new();
+                        unsafe {
+                            [<mclBn $name _mulVec>](&mut z, points.as_mut_ptr(), scalars.as_ptr(), points.len());
+                        }
+
+                        Ok(z)
+                    }
+
+                    #[cfg(feature = "pippenger")]
+                    {
+                        Ok(multi_exp::pippenger(points, scalars))
+                    }
+                }
+            }
+        }
+    };
+}
+
 // Implement API methods for library types
 impl_api!(MclBnFr, Id);
 impl_api!(MclBnFr, SecretKey);
 impl_api!(MclBnG2, PublicKey);
 impl_api!(MclBnG1, Signature);
 
+// Implement equality for public types, secret keys are compared in constant time instead
+impl_eq!(Id);
+impl_eq!(PublicKey);
+impl_eq!(Signature);
+
 // Implement arithmetic operations for supported types
 impl_ops!(SecretKey);
 impl_ops!(PublicKey);
 impl_ops!(Signature);
 
+// Implement multiplication by scalars for supported types
+impl_scalar_ops!(SecretKey);
+impl_scalar_ops!(PublicKey);
+impl_scalar_ops!(Signature);
+
+// Implement validity checks for curve point types
+impl_point_checks!(PublicKey);
+impl_point_checks!(Signature);
+
+// Implement parsing from bytes and hex strings
+impl_parse!(Id);
+impl_parse!(SecretKey);
+impl_parse!(PublicKey);
+impl_parse!(Signature);
+
+// Implement hex formatting, secret keys are left out to keep them from being printed by accident
+impl_display!(Id);
+impl_display!(PublicKey);
+impl_display!(Signature);
+
+// Implement hashing and ordering so keys can be used in maps and sets
+impl_hash_ord!(Id);
+impl_hash_ord!(PublicKey);
+impl_hash_ord!(Signature);
+
+// Implement compressed and uncompressed encodings
+impl_point_encoding!(PublicKey, G2, 2);
+impl_point_encoding!(Signature, G1, 1);
+
 // Implement secret sharing APIs for supported types
 impl_sharing!(SecretKey);
 impl_sharing!(PublicKey);
@@ -559,3 +1912,38 @@ impl_sharing!(PublicKey);
 impl_recover!(SecretKey);
 impl_recover!(PublicKey);
 impl_recover!(Signature);
+
+// Implement conversions between library and pairing engine types
+impl_conversions!(MclBnFr, Id);
+impl_conversions!(MclBnFr, SecretKey);
+impl_conversions!(MclBnG2, PublicKey);
+impl_conversions!(MclBnG1, Signature);
+
+// Implement string conversions for pairing engine types
+impl_mcl_str!(Fr);
+impl_mcl_str!(Fp);
+impl_mcl_str!(G1);
+impl_mcl_str!(G2);
+
+// Implement hashing to pairing engine groups
+impl_hash_to_curve!(G1);
+impl_hash_to_curve!(G2);
+
+// Implement multi-scalar multiplication for pairing engine groups
+impl_multi_exp!(G1);
+impl_multi_exp!(G2);
+
+// Implement arithmetic operations for pairing engine types
+impl_mcl_group_ops!(Fr);
+impl_mcl_op!(Fr, Mul, mul, MulAssign, mul_assign, mul, MclBnFr);
+impl_mcl_op!(Fr, Div, div, DivAssign, div_assign, div, MclBnFr);
+
+impl_mcl_group_ops!(G1);
+impl_mcl_op!(G1, Mul, mul, MulAssign, mul_assign, mul, MclBnFr);
+
+impl_mcl_group_ops!(G2);
+impl_mcl_op!(G2, Mul, mul, MulAssign, mul_assign, mul, MclBnFr);
+
+impl_mcl_eq!(GT);
+impl_mcl_op!(GT, Mul, mul, MulAssign, mul_assign, mul, MclBnGT);
+impl_mcl_op!(GT, Div, div, DivAssign, div_assign, div, MclBnGT);
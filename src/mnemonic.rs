@@ -0,0 +1,136 @@
+//! [BIP-39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki) mnemonic
+//! phrases for backing up and restoring EIP-2333 derived secret keys.
+
+use crate::BlsSecretKey;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256, Sha512};
+use unicode_normalization::UnicodeNormalization;
+
+const WORDLIST: &str = include_str!("mnemonic/english.txt");
+const BITS_PER_WORD: usize = 11;
+const SEED_ROUNDS: u32 = 2048;
+const SEED_SIZE: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    phrase: String,
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// Generates a new mnemonic of 12, 15, 18, 21 or 24 words from system randomness.
+    pub fn generate(word_count: usize) -> Result<Self, ()> {
+        if !is_valid_word_count(word_count) {
+            return Err(());
+        }
+
+        let mut entropy = vec![0u8; word_count / 3 * 4];
+        OsRng.fill_bytes(&mut entropy);
+
+        Mnemonic::from_entropy(&entropy)
+    }
+
+    /// Encodes 16 to 32 bytes (in multiples of 4) of entropy as a mnemonic.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, ()> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(());
+        }
+
+        let mut bits = to_bits(entropy);
+        bits.extend(checksum_bits(entropy));
+
+        let words = wordlist();
+        let phrase = bits
+            .chunks(BITS_PER_WORD)
+            .map(|chunk| words[chunk.iter().fold(0, |acc, bit| acc << 1 | *bit as usize)])
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Ok(Mnemonic {
+            phrase,
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Parses a mnemonic phrase, validating its words and checksum.
+    pub fn from_phrase(phrase: &str) -> Result<Self, ()> {
+        let phrase = phrase.nfkd().collect::<String>();
+        let words = wordlist();
+
+        let mut bits = Vec::new();
+        let mut word_count = 0;
+        for word in phrase.split_whitespace() {
+            let index = words.binary_search(&word).map_err(|_| ())?;
+            bits.extend((0..BITS_PER_WORD).rev().map(|i| (index >> i) & 1 == 1));
+            word_count += 1;
+        }
+
+        if !is_valid_word_count(word_count) {
+            return Err(());
+        }
+
+        let checksum_size = bits.len() / 33;
+        let entropy_bits = &bits[..bits.len() - checksum_size];
+        let entropy = entropy_bits
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |acc, bit| acc << 1 | *bit as u8))
+            .collect::<Vec<u8>>();
+
+        if checksum_bits(&entropy) != bits[entropy_bits.len()..] {
+            return Err(());
+        }
+
+        Mnemonic::from_entropy(&entropy)
+    }
+
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Computes the 64-byte BIP-39 seed protected by an optional `passphrase`.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; SEED_SIZE] {
+        let salt = format!("mnemonic{}", passphrase).nfkd().collect::<String>();
+
+        let mut seed = [0u8; SEED_SIZE];
+        pbkdf2::pbkdf2_hmac::<Sha512>(
+            self.phrase.as_bytes(),
+            salt.as_bytes(),
+            SEED_ROUNDS,
+            &mut seed,
+        );
+
+        seed
+    }
+
+    /// Derives the EIP-2333 secret key at `path` (e.g. `m/12381/3600/0/0/0`) from the mnemonic.
+    pub fn to_secret_key(&self, passphrase: &str, path: &str) -> Result<BlsSecretKey, ()> {
+        BlsSecretKey::derive_path(&self.to_seed(passphrase), path)
+    }
+}
+
+fn is_valid_word_count(word_count: usize) -> bool {
+    matches!(word_count, 12 | 15 | 18 | 21 | 24)
+}
+
+fn wordlist() -> Vec<&'static str> {
+    WORDLIST.lines().collect()
+}
+
+fn to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+        .collect()
+}
+
+fn checksum_bits(entropy: &[u8]) -> Vec<bool> {
+    let mut bits = to_bits(&Sha256::digest(entropy));
+    bits.truncate(entropy.len() * 8 / 32);
+
+    bits
+}
@@ -0,0 +1,72 @@
+//! Pippenger's bucket method for multi-scalar multiplication, used instead of mcl's `mulVec`
+//! when the `pippenger` feature is enabled.
+
+use crate::{mclBnFr_getLittleEndian, MclBnFr, MCLBN_FR_UNIT_SIZE};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::AddAssign;
+
+const FR_SIZE: usize = MCLBN_FR_UNIT_SIZE as usize * 8;
+
+pub(crate) fn pippenger<T>(points: &[T], scalars: &[MclBnFr]) -> T
+where
+    T: Clone + Default + AddAssign,
+{
+    let scalars = scalars.iter().map(to_le_bytes).collect::<Vec<_>>();
+    let window = window_size(points.len());
+
+    let mut acc = T::default();
+    for start in (0..FR_SIZE * 8).step_by(window).rev() {
+        for _ in 0..window {
+            let double = acc.clone();
+            acc += double;
+        }
+
+        // Bucket `i` collects the points whose scalar has `i + 1` in the current window
+        let mut buckets = vec![T::default(); (1 << window) - 1];
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let digit = window_digit(scalar, start, window);
+            if digit != 0 {
+                buckets[digit - 1] += point.clone();
+            }
+        }
+
+        // Computes `sum((i + 1) * buckets[i])` with running sums
+        let mut running = T::default();
+        let mut sum = T::default();
+        for bucket in buckets.into_iter().rev() {
+            running += bucket;
+            sum += running.clone();
+        }
+
+        acc += sum;
+    }
+
+    acc
+}
+
+fn window_size(num_points: usize) -> usize {
+    if num_points < 32 {
+        3
+    } else {
+        // Roughly ln(n) + 2
+        (64 - (num_points as u64).leading_zeros() as usize) * 69 / 100 + 2
+    }
+}
+
+fn window_digit(scalar: &[u8; FR_SIZE], start: usize, window: usize) -> usize {
+    (start..(start + window).min(FR_SIZE * 8))
+        .rev()
+        .fold(0, |acc, bit| {
+            acc << 1 | ((scalar[bit / 8] >> (bit % 8)) & 1) as usize
+        })
+}
+
+fn to_le_bytes(scalar: &MclBnFr) -> [u8; FR_SIZE] {
+    let mut buf = [0u8; FR_SIZE];
+    unsafe {
+        mclBnFr_getLittleEndian(buf.as_mut_ptr(), buf.len(), scalar);
+    }
+
+    buf
+}
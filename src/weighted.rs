@@ -0,0 +1,142 @@
+//! Weighted threshold signatures, where a signature needs members holding enough combined weight
+//! (e.g. more than 2/3 of the stake) rather than a fixed number of members.
+//!
+//! A member with weight `w` holds `w` ordinary shares of the group key, each under its own
+//! `BlsId`, and signs with all of them. Any set of members whose weights add up to the threshold
+//! therefore holds enough shares for `BlsSignature::recover`.
+
+use crate::{BlsId, BlsSecretKey, BlsSignature};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+
+/// Largest total weight accepted by `WeightedScheme::new`.
+pub const MAX_TOTAL_WEIGHT: usize = 1 << 16;
+
+#[derive(Debug, Clone)]
+pub struct WeightedScheme {
+    threshold: usize,
+    ids: Vec<Vec<BlsId>>,
+}
+
+impl WeightedScheme {
+    /// Assigns consecutive ids, starting at 1, to members in order of `weights`.
+    ///
+    /// Weights are share counts, and every share costs a key and a signature, so raw stake must be
+    /// scaled down to small integers first, with a total of at most `MAX_TOTAL_WEIGHT`.
+    /// `threshold` is the combined weight needed to sign and must not exceed the total weight.
+    pub fn new(weights: &[usize], threshold: usize) -> Result<Self, ()> {
+        let total_weight = weights
+            .iter()
+            .try_fold(0usize, |total, weight| total.checked_add(*weight))
+            .ok_or(())?;
+        if total_weight > MAX_TOTAL_WEIGHT || threshold == 0 || threshold > total_weight {
+            return Err(());
+        }
+
+        let mut next_id = 1;
+        let ids = weights
+            .iter()
+            .map(|weight| {
+                let ids = (next_id..next_id + *weight as u64)
+                    .map(|id| BlsId::try_from(id).expect("ids start at 1"))
+                    .collect::<Vec<BlsId>>();
+                next_id += *weight as u64;
+
+                ids
+            })
+            .collect();
+
+        Ok(WeightedScheme { threshold, ids })
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn total_weight(&self) -> usize {
+        self.ids.iter().map(Vec::len).sum()
+    }
+
+    pub fn num_members(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn weight(&self, member: usize) -> Option<usize> {
+        self.ids.get(member).map(Vec::len)
+    }
+
+    /// Ids of the shares held by `member`.
+    pub fn ids(&self, member: usize) -> Option<&[BlsId]> {
+        self.ids.get(member).map(Vec::as_slice)
+    }
+
+    /// Creates the shares of `member` from `threshold` master secret keys, see
+    /// `BlsSecretKey::new_master_keys`.
+    pub fn shares(&self, msk: &[BlsSecretKey], member: usize) -> Result<Vec<BlsSecretKey>, ()> {
+        let ids = self.ids(member).ok_or(())?;
+        if msk.len() != self.threshold {
+            return Err(());
+        }
+
+        ids.iter()
+            .map(|id| BlsSecretKey::new_share(&msk[0], self.threshold, id))
+            .collect()
+    }
+
+    pub fn combiner(&self) -> WeightedCombiner<'_> {
+        WeightedCombiner {
+            scheme: self,
+            added: vec![false; self.num_members()],
+            ids: Vec::with_capacity(self.threshold),
+            sigs: Vec::with_capacity(self.threshold),
+        }
+    }
+}
+
+/// Collects partial signatures from members until their combined weight meets the threshold.
+///
+/// Partial signatures aren't verified here, check them against the member's share public keys
+/// before adding them.
+#[derive(Debug, Clone)]
+pub struct WeightedCombiner<'a> {
+    scheme: &'a WeightedScheme,
+    added: Vec<bool>,
+    ids: Vec<BlsId>,
+    sigs: Vec<BlsSignature>,
+}
+
+impl<'a> WeightedCombiner<'a> {
+    /// Adds the signatures of `member`, one per share in the order of `WeightedScheme::ids`.
+    pub fn add(&mut self, member: usize, sigs: &[BlsSignature]) -> Result<(), ()> {
+        let ids = self.scheme.ids(member).ok_or(())?;
+        if self.added[member] || sigs.len() != ids.len() {
+            return Err(());
+        }
+
+        self.added[member] = true;
+        self.ids.extend_from_slice(ids);
+        self.sigs.extend_from_slice(sigs);
+
+        Ok(())
+    }
+
+    /// Combined weight of the members added so far.
+    pub fn weight(&self) -> usize {
+        self.sigs.len()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.weight() >= self.scheme.threshold
+    }
+
+    /// Recovers the group signature once the threshold is met.
+    pub fn combine(&self) -> Result<BlsSignature, ()> {
+        if !self.is_complete() {
+            return Err(());
+        }
+
+        let threshold = self.scheme.threshold;
+        BlsSignature::recover(&self.sigs[..threshold], &self.ids[..threshold], threshold)
+    }
+}
@@ -1,5 +1,8 @@
 mod id {
-    use bls_sys::{bls_init, BlsId, CurveType};
+    use std::collections::BTreeSet;
+    use std::convert::TryFrom;
+
+    use bls_sys::{bls_init, BlsId, CurveType, IoMode};
 
     #[test]
     pub fn id_serde_roundtrip() {
@@ -64,10 +67,96 @@ mod id {
         let dec_str = id.get_hex_str().unwrap();
         assert_eq!("2a", &dec_str);
     }
+
+    #[test]
+    pub fn id_io_modes() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let id = BlsId::try_from(42u64).unwrap();
+
+        assert_eq!("101010", id.get_str(IoMode::Bin).unwrap());
+        assert_eq!("42", id.get_str(IoMode::Dec).unwrap());
+        assert_eq!("0b101010", id.get_str(IoMode::BinPrefix).unwrap());
+        assert_eq!("0x2a", id.get_str(IoMode::HexPrefix).unwrap());
+    }
+
+    #[test]
+    pub fn id_from_str_display() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let id = BlsId::try_from(42u64).unwrap();
+        let hex_str = format!("2a{}", "00".repeat(31));
+
+        assert_eq!(hex_str, format!("{:x}", id));
+        assert_eq!(format!("0x{}", hex_str), id.to_string());
+        assert_eq!(id, id.to_string().parse().unwrap());
+    }
+
+    #[test]
+    pub fn id_from_bytes_le() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let mut node_id = [0u8; 32];
+        node_id[0] = 42;
+        let id = BlsId::from_bytes_le(&node_id).unwrap();
+
+        let mut id_actual = BlsId::new();
+        id_actual.set_int(42);
+
+        assert_eq!(id, id_actual);
+        assert_eq!(id, BlsId::try_from(42u64).unwrap());
+
+        // The share at zero is the master secret
+        assert!(BlsId::from_bytes_le(&[0u8; 32]).is_err());
+        assert!(BlsId::try_from(0u64).is_err());
+
+        // The group order and anything above it isn't reduced
+        let order = [
+            0x01, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0x02, 0xa4,
+            0xbd, 0x53, 0x05, 0xd8, 0xa1, 0x09, 0x08, 0xd8, 0x39, 0x33, 0x48, 0x7d, 0x9d, 0x29,
+            0x53, 0xa7, 0xed, 0x73,
+        ];
+        assert!(BlsId::from_bytes_le(&order).is_err());
+        assert!(BlsId::from_bytes_le(&[0xff; 32]).is_err());
+
+        let mut below_order = order;
+        below_order[0] = 0;
+        assert!(BlsId::from_bytes_le(&below_order).is_ok());
+    }
+
+    #[test]
+    pub fn id_ord() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        // Ordered by value, not by the text of the hex string
+        let ids = [16u64, 10, 255, 1]
+            .iter()
+            .map(|x| BlsId::try_from(*x).unwrap())
+            .collect::<BTreeSet<BlsId>>();
+        let sorted = ids
+            .iter()
+            .map(|id| id.get_str(IoMode::Dec).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["1", "10", "16", "255"], sorted);
+    }
+
+    #[test]
+    pub fn id_hash_from() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let id1 = BlsId::hash_from(b"node 1").unwrap();
+        let id2 = BlsId::hash_from(b"node 2").unwrap();
+
+        assert_eq!(id1, BlsId::hash_from(b"node 1").unwrap());
+        assert_ne!(id1, id2);
+    }
 }
 
 mod secret_key {
+    use std::convert::TryFrom;
+
     use bls_sys::{bls_init, BlsSecretKey, CurveType};
+    use subtle::ConstantTimeEq;
 
     #[test]
     pub fn secret_key_serde_roundtrip() {
@@ -85,6 +174,39 @@ mod secret_key {
         assert_eq!(secret_key, secret_key2);
     }
 
+    #[test]
+    pub fn secret_key_from_str() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let mut secret_key = BlsSecretKey::new();
+        secret_key.set_dec_str("42").unwrap();
+
+        let mut buf = [0u8; 32];
+        secret_key.serialize(&mut buf).unwrap();
+
+        assert_eq!(secret_key, BlsSecretKey::try_from(&buf[..]).unwrap());
+        assert_eq!(
+            secret_key,
+            format!("0x{}", hex::encode(buf)).parse().unwrap()
+        );
+        assert!("0x2a".parse::<BlsSecretKey>().is_err());
+    }
+
+    #[test]
+    pub fn secret_key_ct_eq() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk1 = BlsSecretKey::new_random().unwrap();
+        let sk2 = BlsSecretKey::new_random().unwrap();
+
+        assert!(bool::from(sk1.ct_eq(&sk1.clone())));
+        assert!(!bool::from(sk1.ct_eq(&sk2)));
+
+        // Keys reached by different computations compare equal
+        assert!(bool::from((sk1.clone() + sk2.clone() - sk2).ct_eq(&sk1)));
+        assert_ne!(sk1, BlsSecretKey::new());
+    }
+
     #[test]
     pub fn secret_key_dec_str_set() {
         bls_init(CurveType::Bls12CurveFp381).unwrap();
@@ -135,7 +257,10 @@ mod secret_key {
 }
 
 mod signature {
-    use bls_sys::{bls_init, BlsSecretKey, CurveType};
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::convert::TryFrom;
+
+    use bls_sys::{bls_init, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType, IoMode, MclBnG2};
 
     #[test]
     pub fn sign_verify_ok() {
@@ -156,10 +281,197 @@ mod signature {
         let diff_pk = BlsSecretKey::new_random().unwrap().to_public_key();
         assert!(!sig.verify(&diff_pk, &msg[..]));
     }
+
+    #[test]
+    pub fn aggregate_verify() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sks = (0..3)
+            .map(|_| BlsSecretKey::new_random().unwrap())
+            .collect::<Vec<_>>();
+        let pks = sks.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+        let msgs: [&[u8]; 3] = [b"message 1", b"message 2", b"message 3"];
+
+        let agg_sig = sks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(sk, msg)| sk.sign(msg))
+            .fold(BlsSignature::new(), |acc, sig| acc + sig);
+
+        assert!(agg_sig.verify_aggregate(&pks, &msgs));
+
+        // Messages signed by the wrong keys
+        assert!(!agg_sig.verify_aggregate(&pks, &[msgs[1], msgs[0], msgs[2]]));
+
+        // Missing signer
+        assert!(!agg_sig.verify_aggregate(&pks[..2], &msgs[..2]));
+
+        // Duplicate messages are rejected
+        let same_msg_sig = sks[0].sign(msgs[0]) + sks[1].sign(msgs[0]);
+        assert!(!same_msg_sig.verify_aggregate(&pks[..2], &[msgs[0], msgs[0]]));
+    }
+
+    #[test]
+    pub fn batch_verify() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sks = (0..4)
+            .map(|_| BlsSecretKey::new_random().unwrap())
+            .collect::<Vec<_>>();
+        let pks = sks.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+        let msgs: [&[u8]; 4] = [b"message 1", b"message 2", b"message 2", b"message 3"];
+        let mut sigs = sks
+            .iter()
+            .zip(msgs.iter())
+            .map(|(sk, msg)| sk.sign(msg))
+            .collect::<Vec<_>>();
+
+        assert!(BlsSignature::verify_batch(&sigs, &pks, &msgs));
+        assert!(!BlsSignature::verify_batch(&sigs[..3], &pks, &msgs));
+
+        // Invalid signatures can't cancel each other out
+        let delta = sks[0].sign(b"delta");
+        sigs[0] += delta.clone();
+        sigs[1] -= delta;
+        assert!(!BlsSignature::verify_batch(&sigs, &pks, &msgs));
+    }
+
+    #[test]
+    pub fn point_checks() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let pk = sk.to_public_key();
+        let sig = sk.sign(b"test message");
+
+        assert!(pk.is_valid() && pk.is_in_subgroup() && !pk.is_zero());
+        assert!(sig.is_valid() && sig.is_in_subgroup() && !sig.is_zero());
+
+        assert!(BlsPublicKey::new().is_zero());
+        assert!(BlsSignature::new().is_zero());
+        assert!((pk.clone() - pk).is_zero());
+    }
+
+    #[test]
+    pub fn identity_public_key_rejected() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let msg = b"test message";
+        let zero_pk = BlsPublicKey::new();
+        let zero_sig = BlsSignature::new();
+
+        assert!(!zero_sig.verify(&zero_pk, &msg[..]));
+        assert!(!zero_sig.verify_aggregate(std::slice::from_ref(&zero_pk), &[&msg[..]]));
+        assert!(!BlsSignature::verify_batch(
+            &[zero_sig],
+            &[zero_pk],
+            &[&msg[..]]
+        ));
+    }
+
+    #[test]
+    pub fn long_strings() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let pk = sk.to_public_key();
+        let sig = sk.sign(b"test message");
+
+        // G2 points in decimal don't fit a 128 byte buffer
+        let dec_str = pk.get_str(IoMode::Dec).unwrap();
+        assert!(dec_str.len() > 128);
+        let mut q = MclBnG2::new();
+        q.set_str(&dec_str, IoMode::Dec).unwrap();
+        assert_eq!(pk, BlsPublicKey::from(q));
+
+        let mut pk2 = BlsPublicKey::new();
+        pk2.set_dec_str(&pk.get_dec_str().unwrap()).unwrap();
+        assert_eq!(pk, pk2);
+
+        let mut pk2 = BlsPublicKey::new();
+        pk2.set_hex_str(&pk.get_hex_str().unwrap()).unwrap();
+        assert_eq!(pk, pk2);
+
+        let mut sig2 = BlsSignature::new();
+        sig2.set_hex_str(&sig.get_hex_str().unwrap()).unwrap();
+        assert_eq!(sig, sig2);
+
+        // Affine coordinates of the point
+        let coords = pk.get_str(IoMode::Hex).unwrap();
+        assert!(coords.starts_with("1 "));
+        assert_eq!(5, coords.split(' ').count());
+        assert_eq!(3, sig.get_str(IoMode::Dec).unwrap().split(' ').count());
+        assert_eq!("0", BlsSignature::new().get_str(IoMode::Hex).unwrap());
+        assert!(pk.get_str(IoMode::Bin).unwrap().len() > 4 * 300);
+    }
+
+    #[test]
+    pub fn from_str_display() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let pk = sk.to_public_key();
+        let sig = sk.sign(b"test message");
+
+        let mut buf = [0u8; 96];
+        let size = pk.serialize(&mut buf).unwrap();
+        let hex_str = hex::encode(&buf[..size]);
+
+        assert_eq!(hex_str, format!("{:x}", pk));
+        assert_eq!(format!("0x{}", hex_str), format!("{:#x}", pk));
+        assert_eq!(format!("0x{}", hex_str), pk.to_string());
+        assert_eq!(pk, pk.to_string().parse().unwrap());
+        assert_eq!(pk, hex_str.parse().unwrap());
+        assert_eq!(pk, BlsPublicKey::try_from(&buf[..size]).unwrap());
+
+        assert_eq!(sig, sig.to_string().parse().unwrap());
+        assert_eq!(48, format!("{:x}", sig).len() / 2);
+
+        assert!("0xzz".parse::<BlsSignature>().is_err());
+        assert!(BlsPublicKey::try_from(&buf[..size - 1]).is_err());
+    }
+
+    #[test]
+    pub fn hash_ord() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let pks = (0..8)
+            .map(|_| BlsSecretKey::new_random().unwrap().to_public_key())
+            .collect::<Vec<BlsPublicKey>>();
+
+        let mut map = HashMap::new();
+        for (i, pk) in pks.iter().enumerate() {
+            map.insert(pk.clone(), i);
+        }
+        assert_eq!(8, map.len());
+        assert_eq!(Some(&3), map.get(&pks[3]));
+
+        // Ordering follows the affine coordinates as numbers, c1 before c0
+        let coords = |pk: &BlsPublicKey| {
+            let s = pk.get_str(IoMode::Hex).unwrap();
+            let c = s
+                .split(' ')
+                .map(|c| format!("{:0>96}", c))
+                .collect::<Vec<_>>();
+            [c[2].clone(), c[1].clone(), c[4].clone(), c[3].clone()].concat()
+        };
+        let set = pks.iter().cloned().collect::<BTreeSet<BlsPublicKey>>();
+        let sorted = set.iter().map(coords).collect::<Vec<_>>();
+        let mut expected = pks.iter().map(coords).collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(expected, sorted);
+        assert!(set.contains(&(pks[0].clone() + BlsPublicKey::new())));
+
+        let sig = BlsSecretKey::new_random().unwrap().sign(b"test message");
+        let sigs = vec![sig.clone(), sig.clone()]
+            .into_iter()
+            .collect::<HashSet<BlsSignature>>();
+        assert_eq!(1, sigs.len());
+    }
 }
 
 mod arithm {
-    use bls_sys::{bls_init, BlsSecretKey, CurveType};
+    use bls_sys::{bls_init, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType, MclBnFr};
 
     #[test]
     pub fn sec_key_arithm() {
@@ -200,10 +512,121 @@ mod arithm {
         // Verify aggregated signature from diff. secret keys by aggregated public key
         assert!(agg_sig.verify(&agg_pk, &msg[..]));
     }
+
+    #[test]
+    pub fn sec_key_mul_div() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let mut sk1 = BlsSecretKey::new();
+        sk1.set_dec_str("10").unwrap();
+
+        let mut sk2 = BlsSecretKey::new();
+        sk2.set_dec_str("15").unwrap();
+
+        let product = sk1.clone() * &sk2; // 10 * 15 = 150
+        assert_eq!("150", product.get_dec_str().unwrap());
+
+        let quotient = product / &sk2; // 150 / 15 = 10
+        assert_eq!(sk1, quotient);
+
+        assert_eq!(
+            "30",
+            (sk2.clone() * &MclBnFr::from(2)).get_dec_str().unwrap()
+        );
+        assert_eq!(
+            "5",
+            (sk2.clone() / &MclBnFr::from(3)).get_dec_str().unwrap()
+        );
+        assert_eq!("1", (sk2.clone() * &sk2.inverse()).get_dec_str().unwrap());
+        assert_eq!(sk1.clone() - sk2.clone(), -(sk2 - sk1));
+    }
+
+    #[test]
+    pub fn pk_sig_scalar_mul() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let scalar = BlsSecretKey::new_random().unwrap();
+        let msg = b"test message";
+
+        // (sk * x) * Q == (sk * Q) * x and H(m) * (sk * x) == (H(m) * sk) * x
+        let scaled_sk = sk.clone() * &scalar;
+        assert_eq!(scaled_sk.to_public_key(), sk.to_public_key() * &scalar);
+        assert_eq!(scaled_sk.sign(&msg[..]), sk.sign(&msg[..]) * &scalar);
+
+        let fr = MclBnFr::from(scalar.clone());
+        let mut pk = sk.to_public_key();
+        pk *= &fr;
+        assert_eq!(scaled_sk.to_public_key(), pk);
+
+        let mut sig = sk.sign(&msg[..]);
+        sig *= &fr;
+        assert_eq!(scaled_sk.sign(&msg[..]), sig);
+
+        // Negation
+        assert_eq!((-sk.clone()).to_public_key(), -sk.to_public_key());
+        assert_eq!((-sk.clone()).sign(&msg[..]), -sk.sign(&msg[..]));
+        assert_eq!(sk.to_public_key() * &MclBnFr::from(-1), -sk.to_public_key());
+    }
+
+    #[test]
+    pub fn ref_arithm() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sks = ["10", "15", "20"]
+            .iter()
+            .map(|dec| {
+                let mut sk = BlsSecretKey::new();
+                sk.set_dec_str(dec).unwrap();
+                sk
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!("25", (&sks[0] + &sks[1]).get_dec_str().unwrap());
+        assert_eq!("5", (&sks[1] - &sks[0]).get_dec_str().unwrap());
+
+        let mut acc = sks[0].clone();
+        acc += &sks[2];
+        acc -= &sks[1];
+        assert_eq!("15", acc.get_dec_str().unwrap());
+
+        assert_eq!(
+            "45",
+            sks.iter().sum::<BlsSecretKey>().get_dec_str().unwrap()
+        );
+        assert_eq!(
+            "45",
+            sks.into_iter().sum::<BlsSecretKey>().get_dec_str().unwrap()
+        );
+    }
+
+    #[test]
+    pub fn pk_sig_sum() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sks = (0..5)
+            .map(|_| BlsSecretKey::new_random().unwrap())
+            .collect::<Vec<_>>();
+        let pks = sks.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+        let msg = b"test message";
+        let sigs = sks.iter().map(|sk| sk.sign(&msg[..])).collect::<Vec<_>>();
+
+        let agg_pk: BlsPublicKey = pks.iter().sum();
+        let agg_sig: BlsSignature = sigs.iter().sum();
+        assert!(agg_sig.verify(&agg_pk, &msg[..]));
+
+        assert_eq!(agg_pk, pks.into_iter().sum());
+        assert_eq!(agg_sig, sigs.into_iter().sum());
+        assert_eq!(BlsSignature::new(), Vec::<BlsSignature>::new().iter().sum());
+    }
 }
 
 mod secret_sharing {
+    use std::convert::TryFrom;
+
     use bls_sys::{bls_init, BlsId, BlsPublicKey, BlsSecretKey, CurveType};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
     pub fn trivial_secret_sharing() {
@@ -227,4 +650,691 @@ mod secret_sharing {
         let pub2 = BlsPublicKey::recover(&[pub1.clone()], &[id], 1).unwrap();
         assert_eq!(pub1, pub2);
     }
+
+    #[test]
+    pub fn seeded_threshold_sharing() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::random(&mut StdRng::seed_from_u64(1));
+        assert_eq!(sk, BlsSecretKey::random(&mut StdRng::seed_from_u64(1)));
+        assert_ne!(sk, BlsSecretKey::random(&mut StdRng::seed_from_u64(2)));
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let msk = sk.new_master_keys(3, &mut rng);
+        assert_eq!(3, msk.len());
+        assert_eq!(sk, msk[0]);
+        assert_eq!(msk, sk.new_master_keys(3, &mut StdRng::seed_from_u64(3)));
+
+        let ids = (1..=5u64)
+            .map(|x| BlsId::try_from(x).unwrap())
+            .collect::<Vec<BlsId>>();
+        let shares = ids
+            .iter()
+            .map(|id| BlsSecretKey::new_share(&msk[0], 3, id).unwrap())
+            .collect::<Vec<BlsSecretKey>>();
+
+        let recovered = BlsSecretKey::recover(&shares[2..], &ids[2..], 3).unwrap();
+        assert_eq!(sk, recovered);
+    }
+}
+
+mod derive {
+    use bls_sys::{bls_init, parse_path, BlsSecretKey, CurveType};
+
+    // Test vectors from EIP-2333: (seed, master secret key, child index, child secret key)
+    const VECTORS: [(&str, &str, u32, &str); 4] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            0,
+            "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+            3141592653,
+            "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+        ),
+        (
+            "0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+            "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+            4294967295,
+            "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+        ),
+        (
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+            "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+            42,
+            "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+        ),
+    ];
+
+    #[test]
+    pub fn eip2333_vectors() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        for (seed, master_sk, index, child_sk) in VECTORS.iter() {
+            let seed = hex::decode(seed).unwrap();
+
+            let master = BlsSecretKey::derive_master(&seed).unwrap();
+            assert_eq!(*master_sk, master.get_dec_str().unwrap());
+
+            let child = master.derive_child(*index);
+            assert_eq!(*child_sk, child.get_dec_str().unwrap());
+        }
+    }
+
+    #[test]
+    pub fn derive_path() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let seed = hex::decode(VECTORS[1].0).unwrap();
+        let sk = BlsSecretKey::derive_path(&seed, "m/3141592653").unwrap();
+        assert_eq!(VECTORS[1].3, sk.get_dec_str().unwrap());
+
+        let sk = BlsSecretKey::derive_path(&seed, "m/12381/3600/0/0/0").unwrap();
+        let expected = BlsSecretKey::derive_master(&seed)
+            .unwrap()
+            .derive_child(12381)
+            .derive_child(3600)
+            .derive_child(0)
+            .derive_child(0)
+            .derive_child(0);
+        assert_eq!(sk, expected);
+    }
+
+    #[test]
+    pub fn parse_paths() {
+        assert_eq!(Ok(vec![]), parse_path("m"));
+        assert_eq!(
+            Ok(vec![12381, 3600, 0, 0, 0]),
+            parse_path("m/12381/3600/0/0/0")
+        );
+
+        assert!(parse_path("").is_err());
+        assert!(parse_path("12381/3600").is_err());
+        assert!(parse_path("m/").is_err());
+        assert!(parse_path("m/-1").is_err());
+        assert!(parse_path("m/+1").is_err());
+        assert!(parse_path("m/4294967296").is_err());
+    }
+
+    #[test]
+    pub fn short_seed_rejected() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        assert!(BlsSecretKey::derive_master(&[0u8; 31]).is_err());
+    }
+}
+
+mod keystore {
+    use bls_sys::keystore::{Kdf, Keystore, KeystoreError};
+    use bls_sys::{bls_init, BlsSecretKey, CurveType};
+
+    // Test vectors from EIP-2335
+    const PASSWORD: &str = "\u{1d531}\u{1d522}\u{1d530}\u{1d531}\u{1d52d}\u{1d51e}\u{1d530}\u{1d530}\u{1d534}\u{1d52c}\u{1d52f}\u{1d521}\u{1f511}";
+    const SECRET: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "scrypt",
+                "params": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 1,
+                    "r": 8,
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f"
+            }
+        },
+        "description": "This is a test keystore that uses scrypt to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/3141592653/589793238",
+        "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+        "version": 4
+    }"#;
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    fn expected_secret() -> BlsSecretKey {
+        let mut sk = BlsSecretKey::new();
+        sk.set_hex_str(SECRET).unwrap();
+
+        sk
+    }
+
+    #[test]
+    pub fn eip2335_scrypt_vector() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let keystore = Keystore::from_json(SCRYPT_KEYSTORE).unwrap();
+        assert_eq!(expected_secret(), keystore.decrypt(PASSWORD).unwrap());
+    }
+
+    #[test]
+    pub fn eip2335_pbkdf2_vector() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let keystore = Keystore::from_json(PBKDF2_KEYSTORE).unwrap();
+        assert_eq!(expected_secret(), keystore.decrypt(PASSWORD).unwrap());
+        assert_eq!(
+            Err(KeystoreError::InvalidPassword),
+            keystore.decrypt("wrong password")
+        );
+    }
+
+    #[test]
+    pub fn encrypt_decrypt_roundtrip() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let kdfs = [
+            Kdf::Scrypt {
+                n: 1024,
+                r: 8,
+                p: 1,
+            },
+            Kdf::Pbkdf2 { c: 1024 },
+        ];
+
+        for kdf in kdfs.iter() {
+            let keystore = Keystore::encrypt(&sk, "password", "m/12381/3600/0/0/0", *kdf).unwrap();
+            let keystore = Keystore::from_json(&keystore.to_json()).unwrap();
+
+            assert_eq!(4, keystore.version);
+            assert_eq!("m/12381/3600/0/0/0", keystore.path);
+            assert_eq!(sk, keystore.decrypt("password").unwrap());
+            assert_eq!(
+                Err(KeystoreError::InvalidPassword),
+                keystore.decrypt("Password")
+            );
+        }
+    }
+
+    #[test]
+    pub fn public_key_checked() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let other = BlsSecretKey::new_random().unwrap();
+        let kdf = Kdf::Pbkdf2 { c: 1024 };
+
+        // The public key of the vectors is sk * g1
+        let keystore = Keystore::encrypt(&expected_secret(), "password", "", kdf).unwrap();
+        let vector = Keystore::from_json(PBKDF2_KEYSTORE).unwrap();
+        assert_eq!(vector.pubkey, keystore.pubkey);
+
+        let mut keystore = Keystore::encrypt(&sk, "password", "", kdf).unwrap();
+
+        keystore.pubkey = Keystore::encrypt(&other, "password", "", kdf)
+            .unwrap()
+            .pubkey;
+        assert_eq!(
+            Err(KeystoreError::PublicKeyMismatch),
+            keystore.decrypt("password")
+        );
+    }
+
+    #[test]
+    pub fn kdf_params_bounded() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let sk = BlsSecretKey::new_random().unwrap();
+        let encrypt = |kdf| {
+            Keystore::encrypt(&sk, "password", "", kdf)
+                .unwrap()
+                .to_json()
+        };
+        let scrypt = encrypt(Kdf::Scrypt {
+            n: 1024,
+            r: 8,
+            p: 1,
+        });
+        let pbkdf2 = encrypt(Kdf::Pbkdf2 { c: 1024 });
+
+        // Oversized key lengths and KDF costs are rejected before deriving
+        for (json, from, to) in [
+            (&scrypt, "\"dklen\": 32", "\"dklen\": 64"),
+            (&scrypt, "\"n\": 1024", "\"n\": 4194304"),
+            (&scrypt, "\"r\": 8", "\"r\": 4096"),
+            (&scrypt, "\"p\": 1", "\"p\": 1000"),
+            (&pbkdf2, "\"dklen\": 32", "\"dklen\": 16"),
+            (&pbkdf2, "\"c\": 1024", "\"c\": 4294967295"),
+        ]
+        .iter()
+        {
+            assert!(json.contains(from));
+            let keystore = Keystore::from_json(&json.replace(from, to)).unwrap();
+            assert_eq!(
+                Err(KeystoreError::InvalidParams),
+                keystore.decrypt("password")
+            );
+        }
+    }
+}
+
+mod mnemonic {
+    use bls_sys::mnemonic::Mnemonic;
+    use bls_sys::{bls_init, BlsSecretKey, CurveType};
+
+    // Test vectors from BIP-39 with the "TREZOR" passphrase: (entropy, phrase, seed)
+    const VECTORS: [(&str, &str, &str); 3] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    #[test]
+    pub fn bip39_vectors() {
+        for (entropy, phrase, seed) in VECTORS.iter() {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(*phrase, mnemonic.phrase());
+            assert_eq!(*seed, hex::encode(&mnemonic.to_seed("TREZOR")[..]));
+
+            let parsed = Mnemonic::from_phrase(phrase).unwrap();
+            assert_eq!(mnemonic, parsed);
+        }
+    }
+
+    #[test]
+    pub fn invalid_phrases_rejected() {
+        // Bad checksum
+        assert!(Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+        )
+        .is_err());
+
+        // Unknown word
+        assert!(Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon bitcoin"
+        )
+        .is_err());
+
+        // Wrong word count
+        assert!(Mnemonic::from_phrase("abandon abandon about").is_err());
+    }
+
+    #[test]
+    pub fn generate_roundtrip() {
+        for word_count in [12, 15, 18, 21, 24].iter() {
+            let mnemonic = Mnemonic::generate(*word_count).unwrap();
+            assert_eq!(*word_count, mnemonic.phrase().split(' ').count());
+            assert_eq!(mnemonic, Mnemonic::from_phrase(mnemonic.phrase()).unwrap());
+        }
+
+        assert!(Mnemonic::generate(13).is_err());
+    }
+
+    #[test]
+    pub fn restore_secret_key() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        // The seed of the first BIP-39 vector is also the first EIP-2333 test seed
+        let mnemonic = Mnemonic::from_phrase(VECTORS[0].1).unwrap();
+        let master = mnemonic.to_secret_key("TREZOR", "m").unwrap();
+        assert_eq!(
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+            master.get_dec_str().unwrap()
+        );
+
+        let sk = mnemonic
+            .to_secret_key("TREZOR", "m/12381/3600/0/0/0")
+            .unwrap();
+        let restored = Mnemonic::from_phrase(mnemonic.phrase())
+            .unwrap()
+            .to_secret_key("TREZOR", "m/12381/3600/0/0/0")
+            .unwrap();
+        assert_eq!(sk, restored);
+        assert_ne!(
+            sk,
+            mnemonic.to_secret_key("", "m/12381/3600/0/0/0").unwrap()
+        );
+
+        let seed = mnemonic.to_seed("TREZOR");
+        assert_eq!(master, BlsSecretKey::derive_master(&seed).unwrap());
+    }
+}
+
+mod weighted {
+    use bls_sys::weighted::{WeightedScheme, MAX_TOTAL_WEIGHT};
+    use bls_sys::{bls_init, BlsSecretKey, BlsSignature, CurveType};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    pub fn weighted_threshold() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        // Stake of 10, signing needs more than 2/3 of it
+        let weights = [4, 3, 2, 1, 0];
+        let scheme = WeightedScheme::new(&weights, 7).unwrap();
+        assert_eq!(10, scheme.total_weight());
+        assert_eq!(Some(3), scheme.weight(1));
+        assert_eq!(None, scheme.weight(5));
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let sk = BlsSecretKey::random(&mut rng);
+        let pk = sk.to_public_key();
+        let msk = sk.new_master_keys(scheme.threshold(), &mut rng);
+
+        let partial_sigs = (0..weights.len())
+            .map(|member| {
+                let shares = scheme.shares(&msk, member).unwrap();
+                assert_eq!(weights[member], shares.len());
+
+                shares
+                    .iter()
+                    .map(|share| share.sign(b"test message"))
+                    .collect::<Vec<BlsSignature>>()
+            })
+            .collect::<Vec<_>>();
+
+        // Members with weights 4 and 2 fall short, adding the member with weight 1 suffices
+        let mut combiner = scheme.combiner();
+        combiner.add(0, &partial_sigs[0]).unwrap();
+        combiner.add(2, &partial_sigs[2]).unwrap();
+        assert_eq!(6, combiner.weight());
+        assert!(!combiner.is_complete());
+        assert!(combiner.combine().is_err());
+
+        assert!(combiner.add(0, &partial_sigs[0]).is_err());
+        assert!(combiner.add(3, &partial_sigs[1]).is_err());
+        combiner.add(3, &partial_sigs[3]).unwrap();
+        assert!(combiner.is_complete());
+
+        let sig = combiner.combine().unwrap();
+        assert_eq!(sk.sign(b"test message"), sig);
+        assert!(sig.verify(&pk, b"test message"));
+
+        // A different qualifying set recovers the same signature
+        let mut combiner = scheme.combiner();
+        combiner.add(1, &partial_sigs[1]).unwrap();
+        combiner.add(0, &partial_sigs[0]).unwrap();
+        assert_eq!(sig, combiner.combine().unwrap());
+    }
+
+    #[test]
+    pub fn invalid_schemes() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        assert!(WeightedScheme::new(&[1, 2], 0).is_err());
+        assert!(WeightedScheme::new(&[1, 2], 4).is_err());
+        assert!(WeightedScheme::new(&[], 1).is_err());
+        assert!(WeightedScheme::new(&[usize::MAX, 2], 1).is_err());
+        assert!(WeightedScheme::new(&[usize::MAX], 1).is_err());
+        assert!(WeightedScheme::new(&[MAX_TOTAL_WEIGHT, 1], 1).is_err());
+
+        let scheme = WeightedScheme::new(&[1, 2], 2).unwrap();
+        let msk = vec![BlsSecretKey::new_random().unwrap()];
+        assert!(scheme.shares(&msk, 0).is_err());
+    }
+}
+
+mod aggregate {
+    use bls_sys::aggregate::AggregateSignature;
+    use bls_sys::{bls_init, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType};
+
+    const MSG: &[u8] = b"test message";
+
+    fn committee(size: usize) -> (Vec<BlsSecretKey>, Vec<BlsPublicKey>) {
+        let sks = (0..size)
+            .map(|_| BlsSecretKey::new_random().unwrap())
+            .collect::<Vec<_>>();
+        let pks = sks.iter().map(|sk| sk.to_public_key()).collect();
+
+        (sks, pks)
+    }
+
+    #[test]
+    pub fn add_merge_verify() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let (sks, pks) = committee(10);
+
+        let mut first = AggregateSignature::new(10);
+        for i in [0, 3, 9].iter() {
+            first.add(*i, &sks[*i].sign(MSG)).unwrap();
+        }
+        assert!(first.add(3, &sks[3].sign(MSG)).is_err());
+        assert!(first.add(10, &sks[0].sign(MSG)).is_err());
+        assert_eq!(vec![0, 3, 9], first.signers());
+        assert_eq!(&[0b0000_1001, 0b0000_0010], first.bitfield());
+        assert!(first.verify(&pks, MSG));
+        assert!(!first.verify(&pks, b"different message"));
+        assert!(!first.verify(&pks[..9], MSG));
+
+        let mut second = AggregateSignature::new(10);
+        second.add(4, &sks[4].sign(MSG)).unwrap();
+        second.add(5, &sks[5].sign(MSG)).unwrap();
+
+        let mut overlapping = AggregateSignature::new(10);
+        overlapping.add(9, &sks[9].sign(MSG)).unwrap();
+        assert!(first.clone().merge(&overlapping).is_err());
+        assert!(first.clone().merge(&AggregateSignature::new(11)).is_err());
+
+        first.merge(&second).unwrap();
+        assert_eq!(5, first.num_signers());
+        assert!(first.has_signed(4));
+        assert!(!first.has_signed(1));
+        assert!(first.verify(&pks, MSG));
+
+        let expected = [0, 3, 4, 5, 9]
+            .iter()
+            .map(|i| sks[*i].sign(MSG))
+            .sum::<BlsSignature>();
+        assert_eq!(&expected, first.signature());
+    }
+
+    #[test]
+    pub fn from_parts() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let (sks, pks) = committee(3);
+
+        let mut aggregate = AggregateSignature::new(3);
+        aggregate.add(1, &sks[1].sign(MSG)).unwrap();
+
+        let parsed =
+            AggregateSignature::from_parts(aggregate.signature().clone(), aggregate.bitfield(), 3)
+                .unwrap();
+        assert_eq!(aggregate, parsed);
+        assert!(parsed.verify(&pks, MSG));
+
+        // Wrong length and set padding bits are rejected
+        let sig = aggregate.signature().clone();
+        assert!(AggregateSignature::from_parts(sig.clone(), &[0b010, 0], 3).is_err());
+        assert!(AggregateSignature::from_parts(sig, &[0b1010], 3).is_err());
+
+        // Nobody signed
+        assert!(!AggregateSignature::new(3).verify(&pks, MSG));
+    }
+}
+
+mod pairing {
+    use bls_sys::{
+        bls_init, final_exp, miller_loop, pairing, pairing_product_is_one, BlsSecretKey, CurveType,
+        MclBnFr, MclBnG1, MclBnG2, MclBnGT,
+    };
+
+    fn random_points() -> (MclBnG1, MclBnG2) {
+        let sk = BlsSecretKey::new_random().unwrap();
+        let p = MclBnG1::from(sk.sign(b"test message"));
+        let q = MclBnG2::from(sk.to_public_key());
+
+        (p, q)
+    }
+
+    #[test]
+    pub fn fr_arithm() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let x = MclBnFr::from(12);
+        let y = MclBnFr::from(4);
+
+        assert_eq!(MclBnFr::from(16), x.clone() + y.clone());
+        assert_eq!(MclBnFr::from(8), x.clone() - y.clone());
+        assert_eq!(MclBnFr::from(48), x.clone() * y.clone());
+        assert_eq!(MclBnFr::from(3), x.clone() / y.clone());
+        assert_eq!(MclBnFr::from(-12), -x.clone());
+        assert!((x.clone() * x.inverse()).is_one());
+        assert!((x.clone() - x).is_zero());
+
+        let mut z = MclBnFr::from(10);
+        z += MclBnFr::from(5);
+        z *= MclBnFr::from(2);
+        assert_eq!(MclBnFr::from(30), z);
+    }
+
+    #[test]
+    pub fn group_arithm() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let (p, q) = random_points();
+        assert!(p.is_valid());
+        assert!(q.is_valid());
+
+        assert_eq!(p.clone() * MclBnFr::from(2), p.clone() + p.clone());
+        assert_eq!(
+            q.clone() * MclBnFr::from(3),
+            q.clone() + q.clone() + q.clone()
+        );
+        assert!((p.clone() + -p.clone()).is_zero());
+        assert!((q.clone() - q).is_zero());
+
+        let mut r = p.clone();
+        r += p.clone();
+        r -= p.clone();
+        assert_eq!(p, r);
+    }
+
+    #[test]
+    pub fn bilinearity() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let (p, q) = random_points();
+        let a = MclBnFr::new_random().unwrap();
+        let b = MclBnFr::new_random().unwrap();
+
+        let e = pairing(&p, &q);
+        assert!(!e.is_one());
+        assert_eq!(final_exp(&miller_loop(&p, &q)), e);
+
+        let e_ab = pairing(&(p.clone() * a.clone()), &(q.clone() * b.clone()));
+        assert_eq!(e.pow(&(a.clone() * b.clone())), e_ab);
+
+        assert_eq!(
+            pairing(&(p.clone() * a.clone()), &q),
+            pairing(&p, &(q.clone() * a))
+        );
+
+        assert!((e.clone() * e.inverse()).is_one());
+        assert!((e.clone() / e).is_one());
+        assert!(pairing(&MclBnG1::new(), &q).is_one());
+        assert!(MclBnGT::one().is_one());
+    }
+
+    #[test]
+    pub fn multi_exp() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        for n in [0, 1, 5, 40].iter() {
+            let points = (0..*n).map(|_| random_points()).collect::<Vec<_>>();
+            let scalars = (0..*n)
+                .map(|_| MclBnFr::new_random().unwrap())
+                .collect::<Vec<_>>();
+
+            let mut expected_g1 = MclBnG1::new();
+            let mut expected_g2 = MclBnG2::new();
+            for ((p, q), s) in points.iter().zip(scalars.iter()) {
+                expected_g1 += p.clone() * s.clone();
+                expected_g2 += q.clone() * s.clone();
+            }
+
+            let g1_points = points.iter().map(|(p, _)| p.clone()).collect::<Vec<_>>();
+            let g2_points = points.into_iter().map(|(_, q)| q).collect::<Vec<_>>();
+            assert_eq!(
+                expected_g1,
+                MclBnG1::multi_exp(&g1_points, &scalars).unwrap()
+            );
+            assert_eq!(
+                expected_g2,
+                MclBnG2::multi_exp(&g2_points, &scalars).unwrap()
+            );
+        }
+
+        let (p, _) = random_points();
+        assert!(MclBnG1::multi_exp(&[p], &[]).is_err());
+    }
+
+    #[test]
+    pub fn pairing_product() {
+        bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+        let (p, q) = random_points();
+        let a = MclBnFr::new_random().unwrap();
+
+        // e(a * P, Q) * e(-P, a * Q) == 1
+        assert!(pairing_product_is_one(&[
+            (p.clone() * a.clone(), q.clone()),
+            (-p.clone(), q.clone() * a),
+        ]));
+        assert!(!pairing_product_is_one(&[(p, q)]));
+        assert!(pairing_product_is_one(&[]));
+    }
 }
@@ -0,0 +1,119 @@
+use std::fs;
+use std::process::{Command, Output};
+
+fn bls(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_bls"))
+        .args(args)
+        .output()
+        .expect("unable to run the bls binary")
+}
+
+fn bls_ok(args: &[&str]) -> String {
+    let output = bls(args);
+    assert!(
+        output.status.success(),
+        "`bls {}` failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+#[test]
+pub fn sign_verify() {
+    let sk = bls_ok(&["keygen"]);
+    let pk = bls_ok(&["pubkey", &sk]);
+    let sig = bls_ok(&["sign", &sk, "test message"]);
+
+    assert_eq!("valid", bls_ok(&["verify", &pk, &sig, "test message"]));
+
+    let output = bls(&["verify", &pk, &sig, "different message"]);
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("invalid", String::from_utf8_lossy(&output.stdout).trim());
+
+    // Hex-encoded messages are decoded before signing
+    let hex_sig = bls_ok(&["sign", &sk, "0x74657374206d657373616765"]);
+    assert_eq!(sig, hex_sig);
+}
+
+#[test]
+pub fn files() {
+    let dir = std::env::temp_dir().join(format!("bls-cli-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let sk_path = dir.join("sk");
+    let msg_path = dir.join("msg");
+    let sig_path = dir.join("sig");
+    let sk_arg = format!("@{}", sk_path.display());
+    let msg_arg = format!("@{}", msg_path.display());
+    let sig_arg = format!("@{}", sig_path.display());
+
+    bls_ok(&["keygen", "--out", sk_path.to_str().unwrap()]);
+    fs::write(&msg_path, b"\x00\x01binary message\xff").unwrap();
+    bls_ok(&[
+        "sign",
+        &sk_arg,
+        &msg_arg,
+        "--out",
+        sig_path.to_str().unwrap(),
+    ]);
+
+    let pk = bls_ok(&["pubkey", &sk_arg]);
+    assert_eq!("valid", bls_ok(&["verify", &pk, &sig_arg, &msg_arg]));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn aggregate() {
+    let sk1 = bls_ok(&["keygen"]);
+    let sk2 = bls_ok(&["keygen"]);
+    let pk1 = bls_ok(&["pubkey", &sk1]);
+    let pk2 = bls_ok(&["pubkey", &sk2]);
+    let sig1 = bls_ok(&["sign", &sk1, "test message"]);
+    let sig2 = bls_ok(&["sign", &sk2, "test message"]);
+
+    let agg_pk = bls_ok(&["aggregate-pks", &pk1, &pk2]);
+    let agg_sig = bls_ok(&["aggregate-sigs", &sig1, &sig2]);
+
+    assert_eq!(
+        "valid",
+        bls_ok(&["verify", &agg_pk, &agg_sig, "test message"])
+    );
+}
+
+#[test]
+pub fn share_recover() {
+    let sk = bls_ok(&["keygen"]);
+    let pk = bls_ok(&["pubkey", &sk]);
+
+    let shares = bls_ok(&["share", &sk, "2", "1", "2", "3"]);
+    let shares = shares.lines().collect::<Vec<&str>>();
+    assert_eq!(3, shares.len());
+
+    // Any two shares recover the secret key
+    let recovered = bls_ok(&["recover", "--secret-key", shares[0], shares[2]]);
+    assert_eq!(sk, recovered);
+
+    // Partial signatures from any two shares recover the group signature
+    let partial_sigs = shares[1..]
+        .iter()
+        .map(|share| {
+            let (id, share_sk) = share.split_at(share.find(':').unwrap());
+            let sig = bls_ok(&["sign", &share_sk[1..], "test message"]);
+            format!("{}:{}", id, sig)
+        })
+        .collect::<Vec<String>>();
+
+    let sig = bls_ok(&["recover", &partial_sigs[0], &partial_sigs[1]]);
+    assert_eq!(bls_ok(&["sign", &sk, "test message"]), sig);
+    assert_eq!("valid", bls_ok(&["verify", &pk, &sig, "test message"]));
+}
+
+#[test]
+pub fn invalid_arguments() {
+    assert_eq!(Some(2), bls(&[]).status.code());
+    assert_eq!(Some(2), bls(&["unknown"]).status.code());
+    assert_eq!(Some(2), bls(&["pubkey", "not hex"]).status.code());
+    assert_eq!(Some(2), bls(&["share", "00", "3", "1", "2"]).status.code());
+}
@@ -0,0 +1,152 @@
+// The point format is a global setting, so these tests live in their own binary to keep it from
+// changing under the tests in `bls.rs`.
+
+use bls_sys::keystore::{Kdf, Keystore};
+use bls_sys::{
+    bls_init, set_point_format, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType, IoMode,
+    MclBnG1, MclBnG2, PointFormat,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// The standard BLS12-381 generators
+const G1_GENERATOR: &str = "1 17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb 08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+const G2_GENERATOR: &str = "1 024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8 13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e 0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801 0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be";
+
+// ZCash encodings of the generators
+const G1_COMPRESSED: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+const G1_UNCOMPRESSED: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+const G2_COMPRESSED: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+const G2_UNCOMPRESSED: &str = "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb80606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801";
+// Secret key of the EIP-2335 test vectors and its public key
+const KEYSTORE_SECRET: &str = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+const KEYSTORE_PUBKEY: &str = "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07";
+
+fn generators() -> (BlsSignature, BlsPublicKey) {
+    let mut p = MclBnG1::new();
+    p.set_str(G1_GENERATOR, IoMode::Hex).unwrap();
+    let mut q = MclBnG2::new();
+    q.set_str(G2_GENERATOR, IoMode::Hex).unwrap();
+
+    (BlsSignature::from(p), BlsPublicKey::from(q))
+}
+
+// Both cases share one test so they don't race on the global format
+#[test]
+pub fn encodings() {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    round_trips();
+    zcash_vectors();
+    keystore_public_key();
+    hash_ord();
+
+    set_point_format(PointFormat::Herumi);
+}
+
+fn round_trips() {
+    let sk = BlsSecretKey::new_random().unwrap();
+    let pk = sk.to_public_key();
+    let sig = sk.sign(b"test message");
+
+    for format in [PointFormat::Herumi, PointFormat::ZCash].iter() {
+        set_point_format(*format);
+
+        assert_eq!(48, BlsSignature::compressed_size());
+        assert_eq!(96, BlsSignature::uncompressed_size());
+        assert_eq!(96, BlsPublicKey::compressed_size());
+        assert_eq!(192, BlsPublicKey::uncompressed_size());
+
+        let compressed = pk.to_compressed();
+        let uncompressed = pk.to_uncompressed().unwrap();
+        assert_eq!(pk, BlsPublicKey::from_compressed(&compressed).unwrap());
+        assert_eq!(pk, BlsPublicKey::from_uncompressed(&uncompressed).unwrap());
+        assert!(BlsPublicKey::from_compressed(&uncompressed).is_err());
+        assert!(BlsPublicKey::from_uncompressed(&compressed).is_err());
+
+        let compressed = sig.to_compressed();
+        let uncompressed = sig.to_uncompressed().unwrap();
+        assert_eq!(sig, BlsSignature::from_compressed(&compressed).unwrap());
+        assert_eq!(sig, BlsSignature::from_uncompressed(&uncompressed).unwrap());
+        assert!(BlsSignature::from_compressed(&compressed[1..]).is_err());
+
+        // The identity round-trips through both encodings
+        let zero = BlsSignature::new();
+        assert_eq!(
+            zero,
+            BlsSignature::from_compressed(&zero.to_compressed()).unwrap()
+        );
+        assert_eq!(
+            zero,
+            BlsSignature::from_uncompressed(&zero.to_uncompressed().unwrap()).unwrap()
+        );
+    }
+}
+
+fn zcash_vectors() {
+    set_point_format(PointFormat::ZCash);
+
+    let (p, q) = generators();
+    assert_eq!(G1_COMPRESSED, hex::encode(p.to_compressed()));
+    assert_eq!(G1_UNCOMPRESSED, hex::encode(p.to_uncompressed().unwrap()));
+    assert_eq!(G2_COMPRESSED, hex::encode(q.to_compressed()));
+    assert_eq!(G2_UNCOMPRESSED, hex::encode(q.to_uncompressed().unwrap()));
+
+    assert_eq!(
+        p,
+        BlsSignature::from_compressed(&hex::decode(G1_COMPRESSED).unwrap()).unwrap()
+    );
+    assert_eq!(
+        q,
+        BlsPublicKey::from_uncompressed(&hex::decode(G2_UNCOMPRESSED).unwrap()).unwrap()
+    );
+
+    let zero = BlsSignature::new().to_uncompressed().unwrap();
+    assert_eq!(0x40, zero[0]);
+    assert!(zero[1..].iter().all(|b| *b == 0));
+
+    // Flag bits that don't belong to an uncompressed point are rejected
+    let mut flagged = hex::decode(G1_UNCOMPRESSED).unwrap();
+    flagged[0] |= 0x20;
+    assert!(BlsSignature::from_uncompressed(&flagged).is_err());
+}
+
+fn keystore_public_key() {
+    let mut sk = BlsSecretKey::new();
+    sk.set_hex_str(KEYSTORE_SECRET).unwrap();
+
+    // Keystores use the ZCash encoding whatever the global format
+    for format in [PointFormat::Herumi, PointFormat::ZCash].iter() {
+        set_point_format(*format);
+
+        let keystore = Keystore::encrypt(&sk, "", "", Kdf::Pbkdf2 { c: 1 }).unwrap();
+        assert_eq!(KEYSTORE_PUBKEY, keystore.pubkey);
+    }
+}
+
+fn hash_ord() {
+    let pks = (0..8)
+        .map(|_| BlsSecretKey::new_random().unwrap().to_public_key())
+        .collect::<Vec<BlsPublicKey>>();
+
+    let snapshot = || {
+        let mut sorted = pks.clone();
+        sorted.sort();
+        let hashes = pks
+            .iter()
+            .map(|pk| {
+                let mut hasher = DefaultHasher::new();
+                pk.hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect::<Vec<u64>>();
+
+        (sorted, hashes)
+    };
+
+    // Hashes and order survive a change of the point format
+    set_point_format(PointFormat::Herumi);
+    let herumi = snapshot();
+    set_point_format(PointFormat::ZCash);
+    assert_eq!(herumi, snapshot());
+}
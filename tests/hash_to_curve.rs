@@ -0,0 +1,115 @@
+// The map-to mode is global, so these tests live in their own binary to keep it from
+// changing under the signing tests in `bls.rs`.
+
+use bls_sys::{
+    bls_init, set_map_to_mode, CurveType, IoMode, MapToMode, MclBnFp, MclBnFp2, MclBnG1, MclBnG2,
+};
+
+const G1_DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+const G2_DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+// BLS12381G1_XMD:SHA-256_SSWU_RO_ vectors from the IETF hash-to-curve draft: (msg, x, y)
+const G1_VECTORS: [(&str, &str, &str); 2] = [
+    (
+        "",
+        "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+        "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+    ),
+    (
+        "abc",
+        "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+        "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+    ),
+];
+
+// BLS12381G2_XMD:SHA-256_SSWU_RO_ vectors from the IETF hash-to-curve draft:
+// (msg, x_re, x_im, y_re, y_im)
+const G2_VECTORS: [(&str, &str, &str, &str, &str); 2] = [
+    (
+        "",
+        "0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+        "05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+        "0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+        "12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+    ),
+    (
+        "abc",
+        "02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6",
+        "139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8",
+        "1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+        "00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16",
+    ),
+];
+
+fn init() {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+    set_map_to_mode(MapToMode::HashToCurve).unwrap();
+}
+
+#[test]
+pub fn g1_vectors() {
+    init();
+
+    for (msg, x, y) in G1_VECTORS.iter() {
+        let mut expected = MclBnG1::new();
+        expected
+            .set_str(&format!("1 {} {}", x, y), IoMode::Hex)
+            .unwrap();
+
+        let p = MclBnG1::hash_to_curve(msg.as_bytes(), G1_DST).unwrap();
+        assert!(p.is_valid());
+        assert_eq!(expected, p);
+    }
+}
+
+#[test]
+pub fn g2_vectors() {
+    init();
+
+    for (msg, x_re, x_im, y_re, y_im) in G2_VECTORS.iter() {
+        let mut expected = MclBnG2::new();
+        expected
+            .set_str(
+                &format!("1 {} {} {} {}", x_re, x_im, y_re, y_im),
+                IoMode::Hex,
+            )
+            .unwrap();
+
+        let p = MclBnG2::hash_to_curve(msg.as_bytes(), G2_DST).unwrap();
+        assert!(p.is_valid());
+        assert_eq!(expected, p);
+    }
+}
+
+#[test]
+pub fn hash_and_map_to() {
+    init();
+
+    let p = MclBnG1::hash_and_map_to(b"test message").unwrap();
+    assert!(p.is_valid());
+    assert!(!p.is_zero());
+    assert_eq!(p, MclBnG1::hash_and_map_to(b"test message").unwrap());
+    assert_ne!(p, MclBnG1::hash_and_map_to(b"different message").unwrap());
+
+    let q = MclBnG2::hash_and_map_to(b"test message").unwrap();
+    assert!(q.is_valid());
+    assert!(!q.is_zero());
+    assert_ne!(q, MclBnG2::hash_and_map_to(b"different message").unwrap());
+}
+
+#[test]
+pub fn map_to_from_fp() {
+    init();
+
+    let mut x = MclBnFp::new();
+    x.set_str("2a", IoMode::Hex).unwrap();
+    let p = MclBnG1::map_to_from_fp(&x).unwrap();
+    assert!(p.is_valid());
+    assert!(!p.is_zero());
+
+    let mut y = MclBnFp::new();
+    y.set_str("1234", IoMode::Dec).unwrap();
+    let q = MclBnG2::map_to_from_fp2(&MclBnFp2::from_coeffs(x, y)).unwrap();
+    assert!(q.is_valid());
+    assert!(!q.is_zero());
+}
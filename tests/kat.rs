@@ -0,0 +1,119 @@
+// Known-answer tests against the JSON vectors in `tests/kat/`, each file naming where its vectors
+// come from. Only vectors from outside of herumi belong here, so only BLS12-381 is covered so far:
+// the RFC 9380 hash-to-curve vectors and signatures computed with the zkcrypto bls12_381 crate.
+//
+// The curve, point format and map-to mode are global, so every file is checked from a single test
+// in its own binary.
+
+use bls_sys::{
+    bls_init, set_map_to_mode, set_point_format, BlsPublicKey, BlsSecretKey, CurveType, MapToMode,
+    MclBnG1, PointFormat,
+};
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::fs;
+
+const FILES: [&str; 2] = ["bls12_381_rfc9380", "bls12_381_min_sig_zkcrypto"];
+
+// ZCash encoding of the standard BLS12-381 G2 generator
+const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+#[derive(Deserialize)]
+struct KatFile {
+    curve: String,
+    /// Hash-to-curve DST of the signatures, the ciphersuite ID for the IETF BLS signatures.
+    /// Files without one use the herumi defaults.
+    dst: Option<String>,
+    /// Specification or implementation and version the vectors come from.
+    source: String,
+    vectors: Vec<Vector>,
+}
+
+/// Hex encoded secret key, message and the expected serialized public key and signature.
+#[derive(Deserialize)]
+struct Vector {
+    secret_key: String,
+    message: String,
+    public_key: String,
+    signature: String,
+}
+
+fn curve_type(name: &str) -> CurveType {
+    match name {
+        "CurveFp254BNb" => CurveType::CurveFp254BNb,
+        "CurveFp382One" => CurveType::CurveFp382One,
+        "CurveFp382Two" => CurveType::CurveFp382Two,
+        "CurveFp462" => CurveType::CurveFp462,
+        "CurveSNARK1" => CurveType::CurveSNARK1,
+        "Bls12CurveFp381" => CurveType::Bls12CurveFp381,
+        _ => panic!("unknown curve {}", name),
+    }
+}
+
+/// Selects the curve and hash-to-curve settings of `file`, returning false if the linked library lacks it.
+fn configure(file: &KatFile) -> bool {
+    if bls_init(curve_type(&file.curve)).is_err() {
+        return false;
+    }
+    set_point_format(PointFormat::Herumi);
+
+    if let Some(dst) = &file.dst {
+        // The only suite mcl implements
+        assert!(dst.contains("BLS12381G1_XMD:SHA-256_SSWU_RO_"));
+
+        set_point_format(PointFormat::ZCash);
+        set_map_to_mode(MapToMode::HashToCurve).unwrap();
+        MclBnG1::set_dst(dst.as_bytes()).unwrap();
+
+        let generator = BlsPublicKey::from_compressed(&hex::decode(G2_GENERATOR).unwrap()).unwrap();
+        BlsPublicKey::set_generator(&generator).unwrap();
+    }
+
+    true
+}
+
+fn check(name: &str, vector: &Vector) {
+    let sk = BlsSecretKey::try_from(hex::decode(&vector.secret_key).unwrap().as_slice())
+        .unwrap_or_else(|_| panic!("{}: invalid secret key {}", name, vector.secret_key));
+    let msg = hex::decode(&vector.message).unwrap();
+
+    let pk = sk.to_public_key();
+    let sig = sk.sign(&msg);
+
+    assert_eq!(
+        vector.public_key,
+        format!("{:x}", pk),
+        "{}: public key",
+        name
+    );
+    assert_eq!(
+        vector.signature,
+        format!("{:x}", sig),
+        "{}: signature of {}",
+        name,
+        vector.message
+    );
+    assert!(sig.verify(&pk, &msg), "{}: verification", name);
+}
+
+#[test]
+pub fn known_answers() {
+    for name in FILES.iter() {
+        let path = format!("{}/tests/kat/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+        let file: KatFile = serde_json::from_str(&fs::read_to_string(&path).unwrap())
+            .unwrap_or_else(|err| panic!("{}: {}", path, err));
+
+        if !configure(&file) {
+            eprintln!("{}: {} isn't supported, skipping", name, file.curve);
+            continue;
+        }
+
+        assert!(!file.vectors.is_empty(), "{}: no vectors", name);
+
+        // Failures name the source of the vectors that disagree
+        let label = format!("{} ({})", name, file.source);
+        for vector in file.vectors.iter() {
+            check(&label, vector);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use bls_sys::{
+    bls_init, BlsId, BlsPublicKey, BlsSecretKey, BlsSignature, CurveType, IoMode, MclBnFr, MclBnG1,
+    MclBnG2,
+};
+use proptest::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::convert::TryFrom;
+
+fn secret_key() -> impl Strategy<Value = BlsSecretKey> {
+    any::<[u8; 32]>().prop_map(|buf| {
+        let mut sk = BlsSecretKey::new();
+        sk.set_little_endian_mod(&buf);
+        sk
+    })
+}
+
+fn scalar() -> impl Strategy<Value = MclBnFr> {
+    secret_key().prop_map(MclBnFr::from)
+}
+
+fn id() -> impl Strategy<Value = BlsId> {
+    (1..=u64::MAX).prop_map(|x| BlsId::try_from(x).unwrap())
+}
+
+fn msg() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..64)
+}
+
+fn init() {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+}
+
+macro_rules! group_laws {
+    ($name:ident, $strategy:expr) => {
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn $name(a in $strategy, b in $strategy, c in $strategy) {
+                init();
+
+                prop_assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+                prop_assert_eq!(
+                    (a.clone() + b.clone()) + c.clone(),
+                    a.clone() + (b.clone() + c.clone())
+                );
+                prop_assert_eq!((a.clone() + b.clone()) - b.clone(), a.clone());
+                prop_assert_eq!(a.clone() - (b.clone() - c.clone()), (a.clone() - b.clone()) + c.clone());
+                prop_assert_eq!(&a + &b, a.clone() + b.clone());
+            }
+        }
+    };
+}
+
+group_laws!(secret_key_laws, secret_key());
+group_laws!(
+    public_key_laws,
+    secret_key().prop_map(|sk| sk.to_public_key())
+);
+group_laws!(
+    signature_laws,
+    secret_key().prop_map(|sk| sk.sign(b"test message"))
+);
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn mcl_laws(a in scalar(), b in scalar(), c in scalar()) {
+        init();
+
+        prop_assert_eq!(a.clone() + b.clone(), b.clone() + a.clone());
+        prop_assert_eq!((a.clone() + b.clone()) + c.clone(), a.clone() + (b.clone() + c.clone()));
+        prop_assert_eq!((a.clone() + b.clone()) - b.clone(), a.clone());
+
+        let p = MclBnG1::hash_and_map_to(b"g1").unwrap();
+        let q = MclBnG2::hash_and_map_to(b"g2").unwrap();
+        prop_assert_eq!(
+            p.clone() * a.clone() + p.clone() * b.clone(),
+            p.clone() * (a.clone() + b.clone())
+        );
+        prop_assert_eq!(
+            q.clone() * a.clone() - q.clone() * b.clone(),
+            q.clone() * (a.clone() - b.clone())
+        );
+    }
+
+    #[test]
+    fn sign_homomorphism(a in secret_key(), b in secret_key(), msg in msg()) {
+        init();
+
+        let sum = a.clone() + b.clone();
+        prop_assert_eq!(sum.sign(&msg), a.sign(&msg) + b.sign(&msg));
+        prop_assert_eq!(sum.to_public_key(), a.to_public_key() + b.to_public_key());
+        prop_assert!(sum.sign(&msg).verify(&sum.to_public_key(), &msg));
+    }
+
+    #[test]
+    fn sign_verify(sk in secret_key(), msg in msg(), other in msg()) {
+        init();
+
+        let pk = sk.to_public_key();
+        let sig = sk.sign(&msg);
+        prop_assume!(!pk.is_zero());
+
+        prop_assert!(sig.verify(&pk, &msg));
+        prop_assert_eq!(msg == other, sig.verify(&pk, &other));
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn sharing_recovery(
+        (threshold, n) in (1usize..8).prop_flat_map(|t| (Just(t), t..12)),
+        seed in any::<u64>(),
+        msg in msg(),
+    ) {
+        init();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let sk = BlsSecretKey::random(&mut rng);
+        let msk = sk.new_master_keys(threshold, &mut rng);
+        let mpk = msk.iter().map(BlsSecretKey::to_public_key).collect::<Vec<_>>();
+
+        let mut ids = (1..=n as u64).map(|x| BlsId::try_from(x).unwrap()).collect::<Vec<BlsId>>();
+        ids.shuffle(&mut rng);
+        let ids = &ids[..threshold];
+
+        let shares = ids
+            .iter()
+            .map(|id| BlsSecretKey::new_share(&msk[0], threshold, id).unwrap())
+            .collect::<Vec<_>>();
+        let pk_shares = ids
+            .iter()
+            .map(|id| BlsPublicKey::new_share(&mpk[0], threshold, id).unwrap())
+            .collect::<Vec<_>>();
+        let sigs = shares.iter().map(|share| share.sign(&msg)).collect::<Vec<_>>();
+
+        prop_assert_eq!(&sk, &BlsSecretKey::recover(&shares, ids, threshold).unwrap());
+        prop_assert_eq!(
+            sk.to_public_key(),
+            BlsPublicKey::recover(&pk_shares, ids, threshold).unwrap()
+        );
+        prop_assert_eq!(sk.sign(&msg), BlsSignature::recover(&sigs, ids, threshold).unwrap());
+    }
+}
+
+proptest! {
+    #[test]
+    fn id_round_trips(id in id()) {
+        init();
+
+        let mut buf = [0u8; 32];
+        let size = id.serialize(&mut buf).unwrap();
+        prop_assert_eq!(&id, &BlsId::try_from(&buf[..size]).unwrap());
+
+        let mut parsed = BlsId::new();
+        parsed.set_dec_str(&id.get_dec_str().unwrap()).unwrap();
+        prop_assert_eq!(&id, &parsed);
+
+        let mut parsed = BlsId::new();
+        parsed.set_hex_str(&id.get_hex_str().unwrap()).unwrap();
+        prop_assert_eq!(&id, &parsed);
+
+        prop_assert_eq!(&id, &id.to_string().parse::<BlsId>().unwrap());
+    }
+
+    #[test]
+    fn secret_key_round_trips(sk in secret_key()) {
+        init();
+
+        let mut buf = [0u8; 32];
+        let size = sk.serialize(&mut buf).unwrap();
+        prop_assert_eq!(&sk, &BlsSecretKey::try_from(&buf[..size]).unwrap());
+
+        let mut parsed = BlsSecretKey::new();
+        parsed.set_dec_str(&sk.get_dec_str().unwrap()).unwrap();
+        prop_assert_eq!(&sk, &parsed);
+
+        let mut parsed = BlsSecretKey::new();
+        parsed.set_hex_str(&sk.get_hex_str().unwrap()).unwrap();
+        prop_assert_eq!(&sk, &parsed);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn point_round_trips(sk in secret_key(), msg in msg()) {
+        init();
+
+        let pk = sk.to_public_key();
+        let sig = sk.sign(&msg);
+
+        prop_assert_eq!(&pk, &BlsPublicKey::from_compressed(&pk.to_compressed()).unwrap());
+        prop_assert_eq!(&pk, &BlsPublicKey::from_uncompressed(&pk.to_uncompressed().unwrap()).unwrap());
+        prop_assert_eq!(&sig, &BlsSignature::from_compressed(&sig.to_compressed()).unwrap());
+        prop_assert_eq!(&sig, &BlsSignature::from_uncompressed(&sig.to_uncompressed().unwrap()).unwrap());
+
+        prop_assert_eq!(&pk, &pk.to_string().parse::<BlsPublicKey>().unwrap());
+        prop_assert_eq!(&sig, &sig.to_string().parse::<BlsSignature>().unwrap());
+
+        let mut parsed = BlsPublicKey::new();
+        parsed.set_hex_str(&pk.get_hex_str().unwrap()).unwrap();
+        prop_assert_eq!(&pk, &parsed);
+
+        let mut parsed = BlsSignature::new();
+        parsed.set_hex_str(&sig.get_hex_str().unwrap()).unwrap();
+        prop_assert_eq!(&sig, &parsed);
+
+        for mode in [IoMode::Dec, IoMode::Hex, IoMode::HexPrefix].iter() {
+            let mut q = MclBnG2::new();
+            q.set_str(&pk.get_str(*mode).unwrap(), *mode).unwrap();
+            prop_assert_eq!(&pk, &BlsPublicKey::from(q));
+
+            let mut p = MclBnG1::new();
+            p.set_str(&sig.get_str(*mode).unwrap(), *mode).unwrap();
+            prop_assert_eq!(&sig, &BlsSignature::from(p));
+        }
+    }
+}
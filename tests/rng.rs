@@ -0,0 +1,61 @@
+// The installed RNG is global, so these tests live in their own binary to keep it from
+// changing under the tests in `bls.rs`.
+
+use bls_sys::{
+    bls_init, reset_rng, set_rng, BlsSecretKey, BlsSignature, CurveType, MclBnFr, MclBnG1,
+};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Both cases share one test so they don't race on the installed RNG
+#[test]
+pub fn custom_rng() {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    replay();
+    batch_randomizers();
+
+    reset_rng();
+}
+
+fn replay() {
+    set_rng(StdRng::seed_from_u64(42));
+    let sk = BlsSecretKey::new_random().unwrap();
+    let x = MclBnFr::new_random().unwrap();
+
+    // Reinstalling the same seed replays the same keys and scalars
+    set_rng(StdRng::seed_from_u64(42));
+    assert_eq!(sk, BlsSecretKey::new_random().unwrap());
+    assert_eq!(x, MclBnFr::new_random().unwrap());
+
+    reset_rng();
+    assert_ne!(sk, BlsSecretKey::new_random().unwrap());
+}
+
+fn batch_randomizers() {
+    let sks = (0..2)
+        .map(|_| BlsSecretKey::new_random().unwrap())
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(|sk| sk.to_public_key()).collect::<Vec<_>>();
+    let msgs: [&[u8]; 2] = [b"message 1", b"message 2"];
+    let sigs = sks
+        .iter()
+        .zip(msgs.iter())
+        .map(|(sk, msg)| sk.sign(msg))
+        .collect::<Vec<_>>();
+
+    // Predict the scalars of the installed RNG and offset the signatures so that their sum
+    // randomized by those scalars is unchanged
+    set_rng(StdRng::seed_from_u64(7));
+    let r0 = MclBnFr::new_random().unwrap();
+    let r1 = MclBnFr::new_random().unwrap();
+    let delta = MclBnG1::from(sks[0].sign(b"delta"));
+
+    let mut forged = sigs.clone();
+    forged[0] += BlsSignature::from(delta.clone() * r1);
+    forged[1] -= BlsSignature::from(delta * r0);
+
+    set_rng(StdRng::seed_from_u64(7));
+    assert!(!BlsSignature::verify_batch(&forged, &pks, &msgs));
+    assert!(BlsSignature::verify_batch(&sigs, &pks, &msgs));
+}
@@ -0,0 +1,62 @@
+// Strict deserialization is a global setting, so these tests live in their own binary to keep
+// it from changing under the tests in `bls.rs`.
+
+use std::slice;
+
+use bls_sys::{
+    bls_init, set_strict_deserialization, BlsSecretKey, BlsSignature, CurveType, IoMode, MclBnG1,
+};
+
+// A point on the BLS12-381 G1 curve (x = 4) that is outside of the prime-order subgroup
+const NON_SUBGROUP_G1: &str = "1 4 a989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c";
+
+#[test]
+pub fn strict_deserialization() {
+    bls_init(CurveType::Bls12CurveFp381).unwrap();
+
+    set_strict_deserialization(false);
+    let mut p = MclBnG1::new();
+    p.set_str(NON_SUBGROUP_G1, IoMode::Hex).unwrap();
+
+    let sig = BlsSignature::from(p);
+    assert!(sig.is_valid());
+    assert!(!sig.is_zero());
+    assert!(!sig.is_in_subgroup());
+
+    let mut buf = [0u8; 48];
+    let size = sig.serialize(&mut buf).unwrap();
+
+    let mut lenient = BlsSignature::new();
+    assert!(lenient.deserialize(&buf[..size]).is_ok());
+
+    set_strict_deserialization(true);
+    let mut strict = BlsSignature::new();
+    assert!(strict.deserialize(&buf[..size]).is_err());
+    assert!(MclBnG1::new()
+        .set_str(NON_SUBGROUP_G1, IoMode::Hex)
+        .is_err());
+
+    set_strict_deserialization(false);
+    batch_rejects_non_subgroup();
+}
+
+// Shares the test above so that both see the same strictness setting
+fn batch_rejects_non_subgroup() {
+    let mut p = MclBnG1::new();
+    p.set_str(NON_SUBGROUP_G1, IoMode::Hex).unwrap();
+
+    let sk = BlsSecretKey::new_random().unwrap();
+    let pk = sk.to_public_key();
+    let msg: &[u8] = b"test message";
+    let sig = sk.sign(msg);
+    assert!(BlsSignature::verify_batch(
+        slice::from_ref(&sig),
+        slice::from_ref(&pk),
+        &[msg]
+    ));
+
+    // Checked even though lenient deserialization let the point through
+    let tampered = BlsSignature::from(MclBnG1::from(sig) + p);
+    assert!(!tampered.is_in_subgroup());
+    assert!(!BlsSignature::verify_batch(&[tampered], &[pk], &[msg]));
+}